@@ -0,0 +1,206 @@
+// Criterion benchmarks for the exercise algorithms.
+//
+//     cargo bench --bench exercises
+//
+// Besides Criterion's own reports under target/criterion, the run ends by
+// collecting the estimates of every benchmark it ran into a single JSON
+// file, target/criterion/summary.json, so that results can be compared
+// between runs by a script.
+
+use std::env;
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use criterion::measurement::WallTime;
+use criterion::{criterion_group, BenchmarkGroup, BenchmarkId, Criterion, Throughput};
+use serde_json::{json, Map, Value};
+
+use rust_book::fibonacci::{self, Strategy};
+use rust_book::hash_maps;
+use rust_book::slice_type;
+use rust_book::units::convert::{self, Unit};
+use rust_book::units::temperature::{Celsius, Fahrenheit, Kelvin, Rankine};
+
+// The groups this run registered, for the summary to be limited to.
+static GROUPS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn group<'a>(c: &'a mut Criterion, name: &str) -> BenchmarkGroup<'a, WallTime> {
+    GROUPS.lock().unwrap().push(name.to_string());
+    c.benchmark_group(name)
+}
+
+fn fibonacci(c: &mut Criterion) {
+    let mut group = group(c, "fibonacci");
+    for strategy in Strategy::ALL {
+        // The naive recursion takes minutes past n = 40.
+        let sizes: &[u64] = match strategy {
+            Strategy::Recursive => &[10, 20],
+            _ => &[10, 20, 90],
+        };
+        for &n in sizes {
+            group.bench_with_input(BenchmarkId::new(format!("{strategy:?}"), n), &n, |b, &n| {
+                b.iter(|| fibonacci::checked_with::<u64>(strategy, black_box(n)))
+            });
+        }
+    }
+    for n in [100, 1000, 10_000] {
+        group.bench_with_input(BenchmarkId::new("big", n), &n, |b, &n| {
+            b.iter(|| fibonacci::big(black_box(n)))
+        });
+    }
+    group.finish();
+}
+
+fn first_word(c: &mut Criterion) {
+    let mut group = group(c, "first_word");
+    let short = String::from("hello world");
+    let long = "a".repeat(10_000) + " world";
+    for (name, text) in [("short", &short), ("long", &long)] {
+        group.throughput(Throughput::Bytes(text.len() as u64));
+        group.bench_with_input(BenchmarkId::new("index", name), text, |b, text| {
+            b.iter(|| slice_type::first_word(black_box(text)))
+        });
+        group.bench_with_input(BenchmarkId::new("string_slice", name), text, |b, text| {
+            b.iter(|| slice_type::first_word_2(black_box(text)).len())
+        });
+        group.bench_with_input(BenchmarkId::new("str_slice", name), text, |b, text| {
+            b.iter(|| slice_type::first_word_3(black_box(text)).len())
+        });
+    }
+    group.finish();
+}
+
+fn word_count(c: &mut Criterion) {
+    let mut group = group(c, "word_count");
+    let words = ["hello", "world", "wonderful", "rust", "book", "notes"];
+    for length in [5, 500, 50_000] {
+        let text: Vec<&str> = words.iter().cycle().take(length).copied().collect();
+        let text = text.join(" ");
+        group.throughput(Throughput::Elements(length as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(length), &text, |b, text| {
+            b.iter(|| hash_maps::word_count(black_box(text)).len())
+        });
+    }
+    group.finish();
+}
+
+fn temperature(c: &mut Criterion) {
+    let mut group = group(c, "temperature");
+    group.bench_function("celsius_to_fahrenheit", |b| {
+        let celsius = Celsius::new(36.6).unwrap();
+        b.iter(|| Fahrenheit::from(black_box(celsius)))
+    });
+    group.bench_function("fahrenheit_to_celsius", |b| {
+        let fahrenheit = Fahrenheit::new(98.6).unwrap();
+        b.iter(|| Celsius::from(black_box(fahrenheit)))
+    });
+    group.bench_function("kelvin_to_rankine", |b| {
+        let kelvin = Kelvin::new(310.0).unwrap();
+        b.iter(|| Rankine::from(black_box(kelvin)))
+    });
+    group.bench_function("checked_new", |b| b.iter(|| Celsius::new(black_box(-40.0))));
+    group.bench_function("convert_units", |b| {
+        let (from, to) = (Unit::parse("°C").unwrap(), Unit::parse("°F").unwrap());
+        b.iter(|| convert::convert(black_box(36.6), from, to))
+    });
+    group.bench_function("parse_expression", |b| {
+        b.iter(|| convert::parse_expression(black_box("36.6 °C in °F")).and_then(|c| c.result()))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, fibonacci, first_word, word_count, temperature);
+
+fn main() {
+    let started = SystemTime::now();
+    benches();
+    let criterion = Criterion::default().configure_from_args();
+    criterion.final_summary();
+
+    // `cargo test --benches` runs each benchmark once, without measuring.
+    if env::args().any(|arg| arg == "--bench") {
+        if let Err(error) = export_summary(started) {
+            eprintln!("Could not write the JSON summary: {error}");
+        }
+    }
+}
+
+// Criterion writes each benchmark's results to
+// <criterion home>/<group>/<benchmark>/new/{benchmark,estimates}.json.
+// Only those of the groups registered by this run and written since it
+// `started` are collected, leaving out older runs and benchmarks that
+// were filtered out.
+fn export_summary(started: SystemTime) -> io::Result<()> {
+    let home = match env::var_os("CRITERION_HOME") {
+        Some(home) => PathBuf::from(home),
+        None => env::var_os("CARGO_TARGET_DIR")
+            .map_or_else(|| PathBuf::from("target"), PathBuf::from)
+            .join("criterion"),
+    };
+
+    let mut results = Map::new();
+    for group in GROUPS.lock().unwrap().iter() {
+        let dir = home.join(group);
+        if dir.is_dir() {
+            collect(&dir, started, &mut results)?;
+        }
+    }
+
+    let path = home.join("summary.json");
+    let summary = json!({ "unit": "ns", "benchmarks": results });
+    fs::write(&path, serde_json::to_string_pretty(&summary)? + "\n")?;
+    println!(
+        "Wrote {} results to {}",
+        results_len(&summary),
+        path.display()
+    );
+    Ok(())
+}
+
+fn collect(dir: &Path, started: SystemTime, results: &mut Map<String, Value>) -> io::Result<()> {
+    let latest = dir.join("new");
+    let estimates = latest.join("estimates.json");
+    if estimates.is_file() {
+        if fs::metadata(&estimates)?.modified()? < started {
+            return Ok(());
+        }
+        let benchmark: Value = serde_json::from_slice(&fs::read(latest.join("benchmark.json"))?)?;
+        let estimates: Value = serde_json::from_slice(&fs::read(latest.join("estimates.json"))?)?;
+        if let Some(id) = benchmark["full_id"].as_str() {
+            results.insert(id.to_string(), summarize(&estimates));
+        }
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect(&path, started, results)?;
+        }
+    }
+    Ok(())
+}
+
+fn summarize(estimates: &Value) -> Value {
+    let statistic = |name: &str| {
+        let estimate = &estimates[name];
+        json!({
+            "estimate": estimate["point_estimate"],
+            "lower_bound": estimate["confidence_interval"]["lower_bound"],
+            "upper_bound": estimate["confidence_interval"]["upper_bound"],
+        })
+    };
+    json!({
+        "mean": statistic("mean"),
+        "median": statistic("median"),
+        "std_dev": statistic("std_dev"),
+    })
+}
+
+fn results_len(summary: &Value) -> usize {
+    summary["benchmarks"].as_object().map_or(0, Map::len)
+}
@@ -0,0 +1,70 @@
+// Naive against memoized Fibonacci, for n = 10, 20, ..., 90.
+//
+//     cargo bench --bench memoization
+//
+// The naive recursion makes about 2F(n + 1) calls, so each step of n
+// multiplies its time by the golden ratio. It is only timed while that
+// predicts under two seconds per call; past that the prediction is printed
+// instead.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use rust_book::fibonacci;
+
+const GOLDEN_RATIO: f64 = 1.618_033_988_749_895;
+const NAIVE_LIMIT: Duration = Duration::from_secs(2);
+
+fn main() {
+    println!("{:>4}  {:>24}  {:>12}", "n", "naive", "memoized");
+
+    let mut last_naive: Option<(u64, Duration)> = None;
+    for n in (10..=90).step_by(10) {
+        let estimate = last_naive
+            .map(|(measured_n, time)| time.mul_f64(GOLDEN_RATIO.powi((n - measured_n) as i32)));
+        let naive = match estimate {
+            Some(estimate) if estimate > NAIVE_LIMIT => {
+                format!("~{} (estimated)", format_duration(estimate))
+            }
+            _ => {
+                let time = time_per_call(|| fibonacci::recursive(black_box(n)));
+                last_naive = Some((n, time));
+                format_duration(time)
+            }
+        };
+        let memoized = time_per_call(|| fibonacci::memoized(black_box(n)));
+
+        println!("{n:>4}  {naive:>24}  {:>12}", format_duration(memoized));
+    }
+}
+
+// Repeats `f` for at least 100 ms, or once if a single call takes longer,
+// and returns the mean time per call.
+fn time_per_call<T>(mut f: impl FnMut() -> T) -> Duration {
+    let started = Instant::now();
+    let mut calls = 0;
+    while calls == 0 || started.elapsed() < Duration::from_millis(100) {
+        black_box(f());
+        calls += 1;
+    }
+    started.elapsed() / calls
+}
+
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs_f64();
+    let units = [
+        (365.25 * 24.0 * 3600.0, "years"),
+        (24.0 * 3600.0, "days"),
+        (3600.0, "h"),
+        (60.0, "min"),
+        (1.0, "s"),
+        (1e-3, "ms"),
+        (1e-6, "µs"),
+    ];
+    for (size, unit) in units {
+        if seconds >= size {
+            return format!("{:.1} {unit}", seconds / size);
+        }
+    }
+    format!("{:.0} ns", seconds * 1e9)
+}
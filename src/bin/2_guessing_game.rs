@@ -1,34 +1,638 @@
-use std::io;
+use std::env;
+use std::io::{self, BufRead, BufReader, IsTerminal, Write};
+use std::net::{TcpListener, TcpStream};
 use std::cmp::Ordering;
-use rand::Rng;
+use std::ops::RangeInclusive;
+use std::process;
+use std::str::FromStr;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use rust_book::guessing_game::{Difficulty, Game, GuessingGame, InvalidGuess, Outcome};
+use rust_book::guessing_game::hints;
+use rust_book::guessing_game::scores::{Mode, Score, ScoreBoard};
+use rust_book::guessing_game::server::Server;
+use rust_book::guessing_game::solver::Solver;
+use rust_book::guessing_game::tui;
+use rust_book::guessing_game::words::{self, Dictionary, InvalidWord, Mark, WordGame};
 
+// Setting this environment variable to anything but "0" has the same
+// effect as passing `--reveal` on the command line.
+const REVEAL_ENV_VAR: &str = "GUESSING_GAME_REVEAL";
+
+const USAGE: &str = "Usage: 2_guessing_game [--reveal] [--seed <u64>]
+    [--difficulty easy|normal|hard] [--min <u32>] [--max <u32>] [--max-attempts <u32>]
+    [--countdown <seconds> | --speedrun <rounds>] [--hints] [--tui] [--name <player>]
+    [--scores [--top <n>]] [--solver] [--words <file>]
+    [--serve <addr>] [--connect <addr>]";
+
+// Command-line options of the game.
+// `seed` is `None` unless the player asked for a specific game with `--seed`.
+// `--min`, `--max` and `--max-attempts` override the matching setting
+// of the difficulty preset.
+// `show_scores` prints the high-score table instead of playing, and
+// `solver` swaps the roles so that the program does the guessing.
+// `hints` adds hotter/colder feedback and a chart at the end of a round,
+// and `tui` plays in a full-screen interface when run in a terminal.
+// `countdown` and `speedrun` select the timed modes.
+// `serve` and `connect` hold the address of a multiplayer server to run
+// or to play on, and `words` the word list of the word-guessing variant.
+struct Options {
+    reveal: bool,
+    seed: Option<u64>,
+    difficulty: Difficulty,
+    min: Option<u32>,
+    max: Option<u32>,
+    max_attempts: Option<u32>,
+    name: Option<String>,
+    show_scores: bool,
+    top: usize,
+    solver: bool,
+    hints: bool,
+    tui: bool,
+    countdown: Option<u64>,
+    speedrun: Option<u32>,
+    serve: Option<String>,
+    connect: Option<String>,
+    words: Option<String>,
+}
 
 fn main() {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{message}");
+            eprintln!("{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    if options.show_scores {
+        show_scores(options.top);
+        return;
+    }
+
+    if let Some(addr) = &options.connect {
+        if let Err(err) = connect(addr) {
+            eprintln!("Connection to {addr} failed: {err}");
+            process::exit(1);
+        }
+        return;
+    }
+
+    let reveal = options.reveal
+        || env::var(REVEAL_ENV_VAR).is_ok_and(|value| value != "0");
+
+    let preset = options.difficulty.range();
+    let min = options.min.unwrap_or(*preset.start());
+    let max = options.max.unwrap_or(*preset.end());
+    if min > max {
+        eprintln!("--min ({min}) must not be greater than --max ({max})");
+        process::exit(2);
+    }
+    let max_attempts = options.max_attempts.or(options.difficulty.max_attempts());
+
+    // Drawing the seed first, rather than using `thread_rng` directly,
+    // means the reveal mode can report everything needed to replay a game,
+    // and `--seed` can replay it.
+    let seed: u64 = options.seed.unwrap_or_else(|| rand::thread_rng().gen());
+
+    if options.solver {
+        solve(min..=max);
+        return;
+    }
+
+    if let Some(path) = &options.words {
+        play_words(path, seed, reveal);
+        return;
+    }
+
+    if let Some(addr) = &options.serve {
+        if let Err(err) = serve(addr, min..=max, seed) {
+            eprintln!("Server on {addr} failed: {err}");
+            process::exit(1);
+        }
+        return;
+    }
+
+    let mode = match (options.countdown, options.speedrun) {
+        (Some(seconds), _) => Mode::Countdown { seconds },
+        (_, Some(rounds)) => Mode::Speedrun { rounds },
+        (None, None) => Mode::Classic,
+    };
+
     println!("Guess the number!");
+    println!("The secret number is between {min} and {max}.");
+    if let Some(max_attempts) = max_attempts {
+        println!("You have {max_attempts} attempts.");
+    }
+    match mode {
+        Mode::Classic => {}
+        Mode::Countdown { seconds } => println!("You have {}, hurry!", in_seconds(seconds)),
+        Mode::Speedrun { rounds } => println!("Win {rounds} rounds as fast as you can!"),
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let new_game = |rng: &mut StdRng| {
+        let game = Game::random(min..=max, rng);
+        if reveal {
+            println!("[reveal] seed: {seed}, secret number: {}", game.secret());
+        }
+        match max_attempts {
+            Some(max_attempts) => game.with_max_attempts(max_attempts),
+            None => game,
+        }
+    };
+
+    // The full-screen interface needs a terminal on both ends, anything
+    // else (a pipe, a file, the integration tests) gets the line mode.
+    if mode == Mode::Classic
+        && options.tui
+        && io::stdin().is_terminal()
+        && io::stdout().is_terminal()
+    {
+        let mut game = new_game(&mut rng);
+        let started = Instant::now();
+        if let Err(err) = tui::run(&mut game, started) {
+            eprintln!("The terminal interface failed: {err}");
+            process::exit(1);
+        }
+        finish_round(&game, options.hints);
+        if game.is_won() {
+            record_score(&options, reveal, mode, game.attempts(), started.elapsed());
+        }
+        return;
+    }
+
+    let input = stdin_lines();
+    let rounds = match mode {
+        Mode::Speedrun { rounds } => rounds,
+        Mode::Classic | Mode::Countdown { .. } => 1,
+    };
+    let deadline = match mode {
+        Mode::Countdown { seconds } => Some(Instant::now() + Duration::from_secs(seconds)),
+        Mode::Classic | Mode::Speedrun { .. } => None,
+    };
+
+    let started = Instant::now();
+    let mut attempts = 0;
 
-    let secret_number = rand::thread_rng()
-        .gen_range(1..=100);
+    for round in 1..=rounds {
+        if rounds > 1 {
+            println!("Round {round} of {rounds}.");
+        }
 
-    println!("The secret secret number is: {secret_number}");
+        let mut game = new_game(&mut rng);
+        let timed_out = play_lines(&mut game, &input, deadline, |game, outcome| {
+            respond(game, outcome, options.hints)
+        });
 
-    println!("Please input your guess.");
+        if timed_out {
+            println!("Time is up!");
+            println!("The secret number was {}.", game.secret());
+        }
+        finish_round(&game, options.hints);
 
-    let mut guess = String::new();
+        if !game.is_won() {
+            if rounds > 1 {
+                println!("Speedrun abandoned after {} of {rounds} rounds.", round - 1);
+            }
+            return;
+        }
+        attempts += game.attempts();
+    }
+
+    let elapsed = started.elapsed();
+    if rounds > 1 {
+        println!(
+            "Speedrun complete: {rounds} rounds in {:.1} seconds.",
+            elapsed.as_secs_f64()
+        );
+        println!("Mean attempts per round: {:.2}", f64::from(attempts) / f64::from(rounds));
+        println!(
+            "Mean time per guess: {:.2} seconds",
+            elapsed.as_secs_f64() / f64::from(attempts)
+        );
+    }
+
+    record_score(&options, reveal, mode, attempts, elapsed);
+}
+
+// Reports how a finished round ended, with the narrowing chart in hint
+// mode.
+fn finish_round(game: &Game, hints: bool) {
+    if game.is_won() {
+        println!("You win!");
+        println!("It took you {} attempts.", game.attempts());
+    } else if game.is_over() {
+        println!("You are out of attempts!");
+        println!("The secret number was {}.", game.secret());
+    }
+
+    if hints && game.is_over() {
+        println!();
+        println!("How the search narrowed:");
+        print!(
+            "{}",
+            hints::narrowing_chart(game.range().clone(), game.history(), game.secret(), 40)
+        );
+    }
+}
+
+fn record_score(options: &Options, reveal: bool, mode: Mode, attempts: u32, elapsed: Duration) {
+    // A revealed or replayed game would make for an unfair entry in the
+    // high scores.
+    if reveal || options.seed.is_some() {
+        println!("Practice game, the score is not recorded.");
+        return;
+    }
+
+    let custom = options.min.is_some() || options.max.is_some() || options.max_attempts.is_some();
+    let difficulty = if custom {
+        String::from("custom")
+    } else {
+        options.difficulty.to_string()
+    };
+    let score = Score {
+        player: player_name(options),
+        mode,
+        difficulty,
+        attempts,
+        elapsed_ms: elapsed.as_millis() as u64,
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs()),
+    };
+    save_score(&score);
+}
+
+// Lines of stdin, read on a thread of their own so that the countdown can
+// stop waiting for a guess.
+// The channel disconnects once stdin is closed.
+fn stdin_lines() -> Receiver<String> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lines().map_while(Result::ok) {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+// The prompt loop of every line mode, one guess per line of input, which
+// returns once the game is over, the input is closed or the deadline has
+// passed. Each answer of the game is handed to `respond` to print.
+// Returns whether the round ended because time was up.
+fn play_lines<G: GuessingGame>(
+    game: &mut G,
+    input: &Receiver<String>,
+    deadline: Option<Instant>,
+    mut respond: impl FnMut(&G, G::Feedback),
+) -> bool {
+    while !game.is_over() {
+        let received = match deadline {
+            Some(deadline) => {
+                let left = deadline.saturating_duration_since(Instant::now());
+                if left.is_zero() {
+                    return true;
+                }
+                let seconds = left.as_secs_f64().ceil() as u64;
+                println!("Please input your guess. ({} left)", in_seconds(seconds));
+                input.recv_timeout(left)
+            }
+            None => {
+                println!("Please input your guess.");
+                input.recv().map_err(RecvTimeoutError::from)
+            }
+        };
 
-    io::stdin()
-        .read_line(&mut guess)
-        .expect("Failed to read line");
+        let guess = match received {
+            Ok(guess) => guess,
+            Err(RecvTimeoutError::Timeout) => return true,
+            // The sender hangs up once stdin is closed, so bail out rather
+            // than prompting forever.
+            Err(RecvTimeoutError::Disconnected) => {
+                println!("No more input, goodbye!");
+                return false;
+            }
+        };
+
+        let feedback = game.submit(&guess);
+        respond(game, feedback);
+    }
+    false
+}
 
-    let guess: u32 = guess
-        .trim()
+fn in_seconds(seconds: u64) -> String {
+    if seconds == 1 {
+        String::from("1 second")
+    } else {
+        format!("{seconds} seconds")
+    }
+}
+
+// Prints the number game's answer to a guess.
+fn respond(game: &Game, outcome: Outcome, hints: bool) {
+    // Only valid guesses are recorded, so the last entry of the
+    // history is the guess that was just compared.
+    if !matches!(outcome, Outcome::Invalid(_)) {
+        if let Some(guess) = game.history().last() {
+            println!("You guessed: {guess}");
+        }
+    }
+
+    match outcome {
+        Outcome::Less => println!("Too small!"),
+        Outcome::Greater => println!("Too big!"),
+        // Reported by the caller once the loop is over.
+        Outcome::Equal | Outcome::OutOfAttempts => {}
+        Outcome::Invalid(InvalidGuess::NotANumber) => {
+            println!("Please type a number!");
+        }
+        Outcome::Invalid(InvalidGuess::OutOfRange) => println!(
+            "Out of range! The secret number is between {} and {}.",
+            game.range().start(),
+            game.range().end()
+        ),
+    }
+
+    if hints && matches!(outcome, Outcome::Less | Outcome::Greater) {
+        print_hints(game);
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        reveal: false,
+        seed: None,
+        difficulty: Difficulty::Normal,
+        min: None,
+        max: None,
+        max_attempts: None,
+        name: None,
+        show_scores: false,
+        top: 10,
+        solver: false,
+        hints: false,
+        tui: false,
+        countdown: None,
+        speedrun: None,
+        serve: None,
+        connect: None,
+        words: None,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--reveal" => options.reveal = true,
+            "--seed" => options.seed = Some(parse_value(&arg, args.next())?),
+            "--difficulty" => options.difficulty = parse_value(&arg, args.next())?,
+            "--min" => options.min = Some(parse_value(&arg, args.next())?),
+            "--max" => options.max = Some(parse_value(&arg, args.next())?),
+            "--max-attempts" => {
+                let max_attempts = parse_value(&arg, args.next())?;
+                if max_attempts == 0 {
+                    return Err(String::from("--max-attempts must be at least 1"));
+                }
+                options.max_attempts = Some(max_attempts);
+            }
+            "--name" => options.name = Some(parse_value(&arg, args.next())?),
+            "--scores" => options.show_scores = true,
+            "--top" => options.top = parse_value(&arg, args.next())?,
+            "--solver" => options.solver = true,
+            "--hints" => options.hints = true,
+            "--tui" => options.tui = true,
+            "--countdown" => {
+                let seconds = parse_value(&arg, args.next())?;
+                if seconds == 0 {
+                    return Err(String::from("--countdown needs at least 1 second"));
+                }
+                options.countdown = Some(seconds);
+            }
+            "--speedrun" => {
+                let rounds = parse_value(&arg, args.next())?;
+                if rounds == 0 {
+                    return Err(String::from("--speedrun needs at least 1 round"));
+                }
+                options.speedrun = Some(rounds);
+            }
+            "--serve" => options.serve = Some(parse_value(&arg, args.next())?),
+            "--connect" => options.connect = Some(parse_value(&arg, args.next())?),
+            "--words" => options.words = Some(parse_value(&arg, args.next())?),
+            _ => return Err(format!("unknown argument: {arg}")),
+        }
+    }
+
+    if options.countdown.is_some() && options.speedrun.is_some() {
+        return Err(String::from("--countdown and --speedrun cannot be combined"));
+    }
+
+    Ok(options)
+}
+
+fn parse_value<T: FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("{flag} expects a value"))?;
+    value
         .parse()
-        .expect("Please type a number!");
+        .map_err(|_| format!("invalid value for {flag}: {value}"))
+}
+
+// The program guesses a number the player has in mind.
+fn solve(range: RangeInclusive<u32>) {
+    let mut solver = Solver::new(range.clone());
 
-    println!("You guessed: {guess}");
+    println!(
+        "Think of a number between {} and {}, I will need at most {} guesses.",
+        range.start(),
+        range.end(),
+        solver.worst_case()
+    );
+
+    while !solver.is_solved() {
+        let guess = solver.next_guess();
+        println!("Is it {guess}? Answer too (s)mall, too (b)ig or (c)orrect.");
+
+        let mut answer = String::new();
+        let bytes_read = io::stdin()
+            .read_line(&mut answer)
+            .expect("Failed to read line");
+
+        if bytes_read == 0 {
+            println!("No more input, goodbye!");
+            return;
+        }
+
+        let answer = match answer.trim().to_lowercase().as_str() {
+            "s" | "small" | "too small" => Ordering::Less,
+            "b" | "big" | "too big" => Ordering::Greater,
+            "c" | "correct" | "y" | "yes" => Ordering::Equal,
+            _ => {
+                println!("Please answer s, b or c.");
+                continue;
+            }
+        };
+
+        if let Err(contradiction) = solver.answer(guess, answer) {
+            println!("That cannot be right: {contradiction}.");
+        }
+    }
+
+    println!("Got it in {} guesses!", solver.guesses());
+}
+
+// The word-guessing variant, using the same prompt and feedback loop as
+// the number game.
+fn play_words(path: &str, seed: u64, reveal: bool) {
+    let dictionary = match Dictionary::load(path) {
+        Ok(dictionary) => dictionary,
+        Err(err) => {
+            eprintln!("Could not read the word list {path}: {err}");
+            process::exit(1);
+        }
+    };
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let Some(mut game) = WordGame::random(&dictionary, &mut rng) else {
+        eprintln!("The word list {path} has no words.");
+        process::exit(1);
+    };
+
+    println!("Guess the word!");
+    println!(
+        "The secret word has {} letters and you have {} attempts.",
+        game.word_length(),
+        words::MAX_ATTEMPTS
+    );
+    println!("[x] means right place, (x) means wrong place.");
+
+    if reveal {
+        println!("[reveal] seed: {seed}, secret word: {}", game.secret());
+    }
+
+    let input = stdin_lines();
+    play_lines(&mut game, &input, None, |_, feedback| match feedback {
+        Ok(letters) => {
+            let feedback: Vec<String> = letters
+                .iter()
+                .map(|letter| match letter.mark {
+                    Mark::Correct => format!("[{}]", letter.letter),
+                    Mark::Present => format!("({})", letter.letter),
+                    Mark::Absent => format!(" {} ", letter.letter),
+                })
+                .collect();
+            println!("{}", feedback.concat());
+        }
+        Err(InvalidWord::WrongLength { expected }) => {
+            println!("Please type a word of {expected} letters.");
+        }
+        Err(InvalidWord::NotInDictionary) => println!("That word is not in the list."),
+        // The loop stops once the game is over.
+        Err(InvalidWord::GameOver) => {}
+    });
+
+    if game.is_won() {
+        println!("You win!");
+        println!("It took you {} attempts.", game.attempts());
+    } else if game.is_over() {
+        println!("You are out of attempts!");
+        println!("The secret word was {}.", game.secret());
+    }
+}
+
+fn print_hints(game: &Game) {
+    let secret = game.secret();
+    if let [.., previous, guess] = game.history() {
+        println!("{}", hints::trend(*previous, *guess, secret));
+    }
+    if let Some(guess) = game.history().last() {
+        println!("{}", hints::distance(*guess, secret));
+    }
+}
+
+fn serve(addr: &str, range: RangeInclusive<u32>, seed: u64) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    println!("Guessing game server listening on {}", listener.local_addr()?);
+    println!("Join with: JOIN <room> <name>, then GUESS <number>");
+    Arc::new(Server::new(range, seed)).serve(listener)
+}
+
+// A bare-bones client: server lines are printed as they arrive while
+// stdin is forwarded line by line.
+fn connect(addr: &str) -> io::Result<()> {
+    let mut stream = TcpStream::connect(addr)?;
+    let reader = BufReader::new(stream.try_clone()?);
+
+    thread::spawn(move || {
+        for line in reader.lines().map_while(Result::ok) {
+            println!("{line}");
+        }
+        process::exit(0);
+    });
+
+    for line in io::stdin().lines() {
+        writeln!(stream, "{}", line?)?;
+    }
+    writeln!(stream, "QUIT")
+}
+
+fn player_name(options: &Options) -> String {
+    options
+        .name
+        .clone()
+        .or_else(|| env::var("USER").ok())
+        .or_else(|| env::var("USERNAME").ok())
+        .unwrap_or_else(|| String::from("anonymous"))
+}
+
+// Failing to save a score should not spoil a won game, so errors are
+// only reported.
+fn save_score(score: &Score) {
+    let Some(board) = ScoreBoard::open_default() else {
+        eprintln!("No data directory found, the score is not recorded.");
+        return;
+    };
+
+    match board.record(score) {
+        Ok(()) => println!("Score recorded for {}.", score.player),
+        Err(err) => eprintln!("Could not record the score in {}: {err}", board.path().display()),
+    }
+}
+
+fn show_scores(top: usize) {
+    let Some(board) = ScoreBoard::open_default() else {
+        eprintln!("No data directory found, there are no scores to show.");
+        process::exit(1);
+    };
+
+    let table = match board.top(top) {
+        Ok(table) => table,
+        Err(err) => {
+            eprintln!("Could not read {}: {err}", board.path().display());
+            process::exit(1);
+        }
+    };
+
+    if table.is_empty() {
+        println!("No scores recorded yet.");
+        return;
+    }
 
-    match guess.cmp(&secret_number) {
-        Ordering::Less => println!("Too small!"),
-        Ordering::Greater => println!("Too big!"),
-        Ordering::Equal => println!("You win!"),
+    for ((mode, difficulty), scores) in &table {
+        println!("{difficulty} ({mode})");
+        for (rank, score) in scores.iter().enumerate() {
+            println!(
+                "{:>3}. {:<16} {:>3} attempts {:>8.1}s  {}",
+                rank + 1,
+                score.player,
+                score.attempts,
+                score.elapsed_ms as f64 / 1000.0,
+                score.date()
+            );
+        }
     }
 }
@@ -36,13 +36,16 @@
 //      * Decimal:  98_777
 //      * Hex:      0xff
 //      * Octal:    0o77
-//      * Binary:   0b111-0000
+//      * Binary:   0b1111_0000
 //      * Byte:     b'A'
 // Note that underscores may be used to separate grouping of numbers,
 // functioning like commas in standard English decimal notation.
 //
 // Rust defaults to i32.
 //
+// `"0xff".parse()` only reads decimal; `rust_book::integers::literal` reads
+// all of the above, with a type suffix such as 255u8 or -3i64.
+//
 // `isize` and `usize` is typically used when indexing a collection
 //
 // Integer Overflow
@@ -58,6 +61,9 @@
 //      * checked_*
 //      * overflowing_*
 //      * saturating_*
+// The `overflow` binary shows the four side by side, and what the plain
+// operator does, for any operation and integer type:
+//      cargo run --bin overflow -- add u8 250 10
 // 
 // Floating-Point Types
 // ////////////////////
@@ -120,8 +126,14 @@
 // the program, which can compile, to panic.
 // This is an example of the safety of programming in Rust.
 // It protects against random memory access.
+//
+// `get` returns an `Option` instead, `None` when out of range, which
+// `rust_book::compound` uses to index arrays and tuples from stdin,
+// with negative indices counting from the end.
 
-use std::io;
+use std::io::{self, IsTerminal, Write};
+
+use rust_book::compound::{Compound, Scope, COMMANDS};
 
 fn main() {
 
@@ -206,18 +218,40 @@ fn main() {
     println!("The first two values of array a are: {first}, {second}");
 
     // Accessing out of range index
-    println!("Please enter an array index.");
-    let mut index = String::new();
-    io::stdin()
-        .read_line(&mut index)
-        .expect("Failed to read line.");
-    
-    let index: usize = index
-        .trim()
-        .parse()
-        .expect("index entered was not a number");
-    
-    let element = a[index];
-
-    println!("The value of the element at index {index} is: {element}");
+    //
+    // `a[index]` would panic for an index past the end. A `Scope` looks
+    // elements up with `get` and returns an error instead, so out of range
+    // input can be asked for again.
+    let mut scope = Scope::new();
+    scope.insert("a", Compound::from(a));
+    scope.insert("months", Compound::from(months));
+    scope.insert("tup", Compound::Tuple(vec![tup.0.into(), tup.1.into(), tup.2.into()]));
+
+    let interactive = io::stdin().is_terminal();
+    if interactive {
+        println!("Index into a, months and tup, e.g. `get a 3` or `get months -1`.");
+        println!("{COMMANDS}");
+    }
+    loop {
+        if interactive {
+            print!("> ");
+            io::stdout().flush().expect("Failed to write to stdout");
+        }
+        let mut line = String::new();
+        if io::stdin()
+            .read_line(&mut line)
+            .expect("Failed to read line.")
+            == 0
+        {
+            break;
+        }
+        match line.trim() {
+            "" => continue,
+            "quit" | "exit" => break,
+            line => match scope.execute(line) {
+                Ok(output) => println!("{output}"),
+                Err(error) => println!("error: {error}"),
+            },
+        }
+    }
 }
@@ -1,55 +1,31 @@
+use rust_book::fibonacci;
+use rust_book::songs::Song;
+use rust_book::template::Format;
+use rust_book::units::temperature::{Celsius, Fahrenheit};
+
 fn main() {
-    let temp_c: f64 = 0.0;
-    let temp_f: f64 = 0.0;
-    println!("{temp_c} in Fahrenheit: {}", c_to_f(temp_c));
-    println!("{temp_f} in Celsius: {}", f_to_c(temp_f));
+    // The conversions used to be `c_to_f` and `f_to_c` over bare `f64`s,
+    // which happily accepted a Fahrenheit value where Celsius was meant.
+    let temp_c = Celsius::new(0.0).unwrap();
+    let temp_f = Fahrenheit::new(0.0).unwrap();
+    println!("{temp_c} in Fahrenheit: {}", Fahrenheit::from(temp_c));
+    println!("{temp_f} in Celsius: {:.2}", Celsius::from(temp_f));
 
+    // Counting from the first number of the sequence, 0, the nth number
+    // is F(n - 1). The old `fibo_n` recursed forever for n = 0 and took
+    // exponential time; `fibonacci::fast_doubling` needs about log2(n)
+    // steps.
     let n: u64 = 13;
-    println!("The {n}th fibonacci number is: {}", fibo_n(n));
+    println!("The {n}th fibonacci number is: {}", fibonacci::fast_doubling(n - 1));
 
     twelve_days();
 }
 
-fn c_to_f(temp_c: f64) -> f64 {
-    temp_c * 9.0 / 5.0 + 32.0
-}
-
-fn f_to_c(temp_f: f64) -> f64 {
-    (temp_f - 32.0) * 5.0 / 9.0
-}
-
-fn fibo_n(n: u64) -> u64 {
-    if n == 1 {
-        0
-    } else if n == 2 {
-        fibo_n(n - 1);
-        1
-    } else {
-        fibo_n(n - 2) + fibo_n(n - 1)
-    }
-}
-
 fn twelve_days() {
-    let gifts = [
-        "A patridge in a pear tree",
-        "Two turtle doves",
-        "Three French hens",
-        "Four calling birds",
-        "Five golden rings",
-        "Six geese a-laying",
-        "Seven swans a-swimming",
-        "Eight maids a-milking",
-        "Nine ladies dancing",
-        "Ten lords a-leaping",
-        "Eleven pipers piping",
-        "Twelve drummers drumming",
-    ];
-
-    for day in 1..13 {
-        println!("On the {day}th day of Christmas my true love gave to me:");
-        for n in (0..day).rev() {
-            let gift = gifts[n];
-            println!("\t{gift}");
-        }
-    }
+    // The gifts used to be hard-coded here, and the days numbered "1th",
+    // "2th" and "3th". The song now lives in data/songs/twelve_days.toml,
+    // and its layout, gifts indented by a tab, in data/templates/song.txt;
+    // see `rust_book::songs` and `rust_book::template`.
+    let song = Song::built_in("twelve-days").unwrap();
+    print!("{}", song.render(Format::Text));
 }
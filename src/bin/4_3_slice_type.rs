@@ -54,6 +54,10 @@
 // automatically clean up that data when the owner goes out of scope means
 // you don't have to write and debug extra code to get this control.
 
+// The three versions of `first_word` discussed above live in
+// `rust_book::slice_type`, where the benchmarks can run them too.
+use rust_book::slice_type::{first_word, first_word_2, first_word_3};
+
 fn main() {
 
     // Calculated variables untied to the state of the state from which they 
@@ -100,58 +104,3 @@ fn main() {
 
     // println!("a: {}, slice: {}", a, slice);
 }
-
-// The function as an example of trying to identify the first word
-// by identifying the first instance of a space in the string is problematic
-// because the return value, an unsigned integer of architecture size, is
-// only meainingful in relation to the string address supplied as an argument.
-//
-// Because it's a separate value from the string, there no guarantee that
-// it will be valid in the future of the program.
-fn first_word(s: &String) -> usize {
-
-    let bytes = s.as_bytes();                       // Create an array of bytes 
-                                                    // from a string using 
-                                                    // as_bytes method
-
-    for (i, &item) in bytes.iter().enumerate() {    // Iterate over each byte
-                                                    // to test for a space, 
-                                                    // including the index of
-                                                    // each byte to return that
-                                                    // index in case of match
-
-        if item == b' ' { return i }                // Return index on find
-
-    }
-
-    s.len()                                         // Return length of array
-                                                    // where no space is found
-}
-
-// Alternative implementation of first_word using string slice
-// notation
-fn first_word_2(s: &String) -> &str {
-    
-    let bytes = s.as_bytes();
-
-    for (i, &item) in bytes.iter().enumerate() {
-        if item == b' ' { return &s[0..i]; }
-    }
-
-    &s[..]
-}
-
-// Third alternative implementation of first_word using updated 
-// signature for the parameters as &str rather than &String
-// The change allows the function to be used with both &String
-// and &str
-fn first_word_3(s: &str) -> &str {
-    
-    let bytes = s.as_bytes();
-
-    for (i, &item) in bytes.iter().enumerate() {
-        if item == b' ' { return &s[0..i]; }
-    }
-
-    &s[..]
-}
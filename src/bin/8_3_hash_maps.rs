@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use rust_book::hash_maps::word_count;
+
 fn main() {
 
     // HashMap<K, V> stores a mapping of keys to values using a hashing function.
@@ -47,12 +49,9 @@ fn main() {
     println!("{:?}", scores);
 
     // Updating a value vased on an existing value
+    // `word_count` counts each word with `entry` and `or_insert`
     let text = "Hello world wonderful world";
-    let mut map2 = HashMap::new();
-    for word in text.split_whitespace() {
-        let count = map2.entry(word).or_insert(0);
-        *count += 1;
-    }
+    let map2 = word_count(text);
     println!("{:?}", map2);
 
 }
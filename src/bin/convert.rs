@@ -0,0 +1,181 @@
+// Unit converter built on `rust_book::units`.
+//
+//     convert 98.6F to C        ->  37.00°C
+//     convert 300K C            ->  26.85°C
+//     convert --precision 4 5 ft in m
+//     printf '1 mi to km\n2 lb to kg\n' | convert
+//     convert --csv sensors.csv --column temp_f --from F --to C --new-column temp_c
+//
+// Without an expression on the command line, one expression per line is
+// read from stdin.
+// With `--csv`, a column of a CSV file (or of stdin for "-") is converted
+// and the file written to `--output`, or stdout; rows that could not be
+// converted are listed on stderr.
+// Exits with 1 if any conversion failed and 2 on a usage error.
+
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::process;
+
+use rust_book::units::convert::{parse_expression, ConversionError, Unit};
+use rust_book::units::csv::{convert_csv, ColumnConversion, Target};
+
+const USAGE: &str = "Usage: convert [--precision <digits>] [<value><unit> [to|in] <unit>]
+       convert --csv <file|-> --column <name> --from <unit> --to <unit>
+               [--new-column <name>] [--output <file>] [--precision <digits>]";
+
+#[derive(Default)]
+struct CsvOptions {
+    input: Option<String>,
+    column: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+    new_column: Option<String>,
+    output: Option<String>,
+}
+
+fn main() {
+    let mut precision = None;
+    let mut csv = CsvOptions::default();
+    let mut words = Vec::new();
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .unwrap_or_else(|| usage_error(&format!("{arg} expects a value")))
+        };
+        match arg.as_str() {
+            "--precision" | "-p" => match value().parse() {
+                Ok(digits) => precision = Some(digits),
+                Err(_) => usage_error("--precision expects a number of digits"),
+            },
+            "--csv" => csv.input = Some(value()),
+            "--column" => csv.column = Some(value()),
+            "--from" => csv.from = Some(value()),
+            "--to" => csv.to = Some(value()),
+            "--new-column" => csv.new_column = Some(value()),
+            "--output" | "-o" => csv.output = Some(value()),
+            "--help" | "-h" => {
+                println!("{USAGE}");
+                return;
+            }
+            _ => words.push(arg),
+        }
+    }
+
+    if csv.input.is_some() {
+        if !words.is_empty() {
+            usage_error("--csv does not take an expression");
+        }
+        convert_file(csv, precision);
+        return;
+    }
+
+    let precision = precision.unwrap_or(2);
+
+    if !words.is_empty() {
+        match convert(&words.join(" "), precision) {
+            Ok(result) => println!("{result}"),
+            Err(err) => {
+                eprintln!("convert: {err}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let mut failed = false;
+    for (number, line) in io::stdin().lock().lines().enumerate() {
+        let line = line.unwrap_or_else(|err| {
+            eprintln!("convert: cannot read stdin: {err}");
+            process::exit(1);
+        });
+        if line.trim().is_empty() {
+            continue;
+        }
+        match convert(&line, precision) {
+            Ok(result) => println!("{result}"),
+            Err(err) => {
+                eprintln!("convert: line {}: {err}", number + 1);
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn convert(expression: &str, precision: usize) -> Result<String, ConversionError> {
+    let conversion = parse_expression(expression)?;
+    let value = conversion.result()?;
+    Ok(conversion.to.format(value, precision))
+}
+
+fn convert_file(options: CsvOptions, precision: Option<usize>) {
+    let required = |value: Option<String>, flag: &str| {
+        value.unwrap_or_else(|| usage_error(&format!("--csv needs {flag}")))
+    };
+    let unit = |name: String| {
+        Unit::parse(&name).unwrap_or_else(|err| {
+            eprintln!("convert: {err}");
+            process::exit(1);
+        })
+    };
+
+    let conversion = ColumnConversion {
+        column: required(options.column, "--column"),
+        from: unit(required(options.from, "--from")),
+        to: unit(required(options.to, "--to")),
+        target: options.new_column.map_or(Target::InPlace, Target::NewColumn),
+        precision,
+    };
+    if conversion.from.dimension != conversion.to.dimension {
+        eprintln!(
+            "convert: cannot convert {} to {}",
+            conversion.from.symbol, conversion.to.symbol
+        );
+        process::exit(1);
+    }
+
+    let input: Box<dyn BufRead> = match options.input.as_deref() {
+        Some("-") | None => Box::new(io::stdin().lock()),
+        Some(path) => Box::new(BufReader::new(or_exit(File::open(path), path))),
+    };
+    let output: Box<dyn Write> = match options.output.as_deref() {
+        Some(path) => Box::new(BufWriter::new(or_exit(File::create(path), path))),
+        None => Box::new(BufWriter::new(io::stdout().lock())),
+    };
+
+    let result = convert_csv(input, output, &conversion, |err| eprintln!("convert: {err}"));
+    match result {
+        Ok(summary) if summary.failed > 0 => {
+            eprintln!(
+                "convert: {} of {} rows could not be converted",
+                summary.failed, summary.rows
+            );
+            process::exit(1);
+        }
+        Ok(_) => {}
+        Err(err) => {
+            eprintln!("convert: {err}");
+            process::exit(1);
+        }
+    }
+}
+
+fn or_exit(file: io::Result<File>, path: &str) -> File {
+    file.unwrap_or_else(|err| {
+        eprintln!("convert: {path}: {err}");
+        process::exit(1);
+    })
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("convert: {message}");
+    eprintln!("{USAGE}");
+    process::exit(2);
+}
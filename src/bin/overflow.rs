@@ -0,0 +1,124 @@
+// Integer overflow explorer built on `rust_book::integers::overflow`.
+//
+//     overflow add u8 250 10
+//     overflow neg i8 -128
+//     overflow pow i32 3 21
+//     overflow mul u16 0xff 0b1_0000_0001
+//
+// prints the result of the operation under every overflow policy, side by
+// side. The operands are integer literals in any notation, see
+// `rust_book::integers::literal`. Without arguments, reads one such
+// command per line from stdin.
+// Exits with 1 if the operation fails and 2 on a usage error.
+
+use std::env;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::process;
+
+use rust_book::integers::overflow::{compare_as, Operation};
+use rust_book::integers::IntegerType;
+
+const USAGE: &str = "Usage: overflow <add|sub|mul|div|pow|neg> <type> <a> [<b>]
+Types: i8, i16, i32, i64, i128, u8, u16, u32, u64, u128";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{USAGE}");
+        return;
+    }
+    if !args.is_empty() {
+        let words: Vec<&str> = args.iter().map(String::as_str).collect();
+        match run(&words) {
+            Ok(report) => print!("{report}"),
+            Err(Error::Usage(message)) => {
+                eprintln!("overflow: {message}");
+                eprintln!("{USAGE}");
+                process::exit(2);
+            }
+            Err(Error::Failed(message)) => {
+                eprintln!("overflow: {message}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let interactive = io::stdin().is_terminal();
+    if interactive {
+        println!("{USAGE}");
+        if cfg!(debug_assertions) {
+            println!("This is a debug build: the plain operators panic on overflow.");
+        } else {
+            println!("This is a release build: the plain operators wrap on overflow.");
+        }
+    }
+    loop {
+        if interactive {
+            print!("> ");
+            io::stdout().flush().expect("Failed to write to stdout");
+        }
+        let mut line = String::new();
+        if io::stdin()
+            .lock()
+            .read_line(&mut line)
+            .expect("Failed to read line")
+            == 0
+        {
+            break;
+        }
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            [] => continue,
+            ["quit" | "exit"] => break,
+            _ => match run(&words) {
+                Ok(report) => print!("{report}"),
+                Err(Error::Usage(message) | Error::Failed(message)) => println!("{message}"),
+            },
+        }
+    }
+}
+
+enum Error {
+    Usage(String),
+    Failed(String),
+}
+
+fn run(words: &[&str]) -> Result<String, Error> {
+    let [operation, integer_type, operands @ ..] = words else {
+        return Err(Error::Usage(
+            "expected an operation, a type and operands".to_string(),
+        ));
+    };
+    let operation: Operation = operation
+        .parse()
+        .map_err(|error| Error::Usage(format!("{error}")))?;
+    let integer_type: IntegerType = integer_type
+        .parse()
+        .map_err(|error| Error::Usage(format!("{error}")))?;
+    let results = compare_as(integer_type, operation, operands)
+        .map_err(|error| Error::Failed(error.to_string()))?;
+
+    // Operands are shown with the type as a suffix, unless they have one
+    // or are bytes, which cannot.
+    let typed = |operand: &str| {
+        if operand.ends_with(integer_type.name()) || operand.starts_with("b'") {
+            operand.to_string()
+        } else {
+            format!("{operand}{integer_type}")
+        }
+    };
+    let mut report = match operands {
+        [a] => format!("{}({})\n", operation.symbol(), typed(a)),
+        _ => format!(
+            "{} {} {}\n",
+            typed(operands[0]),
+            operation.symbol(),
+            typed(operands[1])
+        ),
+    };
+    for (policy, result) in results {
+        report.push_str(&format!("  {policy:<12} {result}\n"));
+    }
+    Ok(report)
+}
@@ -0,0 +1,113 @@
+// Prints a cumulative song, built in or read from a TOML or JSON file.
+//
+//     sing twelve-days
+//     sing --verse 3 old-lady
+//     sing my_song.json
+//     sing --format html old-macdonald > old_macdonald.html
+//     sing --template verses.md --format markdown twelve-days
+//
+// `--format` lays the whole song out with the templates in data/templates,
+// or with the one given by `--template`; see `rust_book::template`.
+// Exits with 1 if the song or template cannot be read and 2 on a usage
+// error.
+
+use std::env;
+use std::fs;
+use std::process;
+
+use rust_book::songs::{Song, BUILT_IN};
+use rust_book::template::{Format, Template};
+
+fn usage() -> String {
+    let names: Vec<&str> = BUILT_IN.iter().map(|(name, _)| *name).collect();
+    format!(
+        "Usage: sing [--verse <number>] <song|file.toml|file.json>
+       sing [--format text|markdown|html] [--template <file>] <song|file.toml|file.json>
+Built-in songs: {}",
+        names.join(", ")
+    )
+}
+
+fn main() {
+    let mut verse = None;
+    let mut format = None;
+    let mut template = None;
+    let mut source = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--verse" | "-v" => match args.next().map(|value| value.parse::<usize>()) {
+                Some(Ok(number)) => verse = Some(number),
+                _ => usage_error("--verse expects a verse number"),
+            },
+            "--format" | "-f" => match args.next().as_deref() {
+                Some("text") => format = Some(Format::Text),
+                Some("markdown" | "md") => format = Some(Format::Markdown),
+                Some("html") => format = Some(Format::Html),
+                _ => usage_error("--format expects text, markdown or html"),
+            },
+            "--template" | "-t" => match args.next() {
+                Some(path) => template = Some(path),
+                None => usage_error("--template expects a file"),
+            },
+            "--help" | "-h" => {
+                println!("{}", usage());
+                return;
+            }
+            _ if source.is_none() && !arg.starts_with('-') => source = Some(arg),
+            _ => usage_error(&format!("unexpected argument {arg}")),
+        }
+    }
+
+    let Some(source) = source else {
+        usage_error("which song?");
+    };
+    if verse.is_some() && (format.is_some() || template.is_some()) {
+        usage_error("--verse cannot be combined with --format or --template");
+    }
+    let song = match Song::built_in(&source) {
+        Some(song) => song,
+        None => Song::load(&source).unwrap_or_else(|error| {
+            eprintln!("Could not read {source}: {error}");
+            process::exit(1);
+        }),
+    };
+
+    if let Some(path) = template {
+        let template = fs::read_to_string(&path)
+            .map_err(|error| error.to_string())
+            .and_then(|source| Template::parse(&source).map_err(|error| error.to_string()))
+            .unwrap_or_else(|error| {
+                eprintln!("Could not read {path}: {error}");
+                process::exit(1);
+            });
+        match song.render_with(&template, format.unwrap_or(Format::Text)) {
+            Ok(text) => print!("{text}"),
+            Err(error) => {
+                eprintln!("Could not render {path}: {error}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    match (verse, format) {
+        (None, Some(format)) => print!("{}", song.render(format)),
+        (None, None) => print!("{song}"),
+        (Some(number), _) => match song.verse(number) {
+            Some(lines) => lines.iter().for_each(|line| println!("{line}")),
+            None => usage_error(&format!(
+                "\"{}\" has verses 1 to {}",
+                song.title,
+                song.verse_count()
+            )),
+        },
+    }
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("sing: {message}");
+    eprintln!("{}", usage());
+    process::exit(2);
+}
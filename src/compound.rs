@@ -0,0 +1,658 @@
+// Compound Types
+// //////////////
+//
+// Named arrays and tuples, as in 3_2_data_types.rs, indexed without
+// panicking. Where `a[index]` panics when `index` is out of range, a
+// `Scope` looks elements up with `get` and `get_mut` and returns an
+// `IndexError` instead.
+//
+// Indices may be negative to count from the end, -1 being the last
+// element, and ranges are written as in Rust, `1..3`, `..=2` or `-2..`.
+//
+// Elements keep the type they were written with, `1u8` being a `u8` and
+// `1` an `i32`, and only an element of the same type may replace them.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::integers::literal::{self, IntegerValue, LiteralError};
+use crate::integers::IntegerType;
+
+/// The commands `Scope::execute` accepts.
+pub const COMMANDS: &str = "Commands:
+  get <name> <index>            the element at index, -1 being the last
+  set <name> <index> <element>  replaces the element at index
+  slice <name> <range>          the elements of an array in range, as 1..3
+  let <name> = <array|tuple>    binds [1, 2, 3] or (500, 6.4, \"text\")
+  show [<name>]                 the bindings, or the one named";
+
+/// The type of an element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Integer(IntegerType),
+    Float,
+    Text,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Kind::Integer(integer_type) => integer_type.fmt(f),
+            Kind::Float => f.pad("f64"),
+            Kind::Text => f.pad("&str"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Element {
+    Integer(IntegerValue),
+    Float(f64),
+    Text(String),
+}
+
+impl Element {
+    pub fn kind(&self) -> Kind {
+        match self {
+            Element::Integer(value) => Kind::Integer(value.integer_type()),
+            Element::Float(_) => Kind::Float,
+            Element::Text(_) => Kind::Text,
+        }
+    }
+
+    /// Parses an integer literal, a float such as `6.4`, or a string in
+    /// double quotes.
+    pub fn parse(text: &str) -> Result<Element, ElementError> {
+        let text = text.trim();
+        if text.starts_with('"') {
+            return Element::parse_as(text, Kind::Text);
+        }
+        match literal::parse_literal(text) {
+            Ok(value) => Ok(Element::Integer(value)),
+            Err(error) => match text.parse() {
+                Ok(value) => Ok(Element::Float(value)),
+                Err(_)
+                    if text.starts_with(|c: char| c.is_ascii_digit() || c == '-')
+                        || text.starts_with("b'") =>
+                {
+                    Err(ElementError::Literal(error))
+                }
+                Err(_) => Err(ElementError::Invalid {
+                    text: text.to_string(),
+                    expected: None,
+                }),
+            },
+        }
+    }
+
+    /// Parses an element of type `kind`.
+    pub fn parse_as(text: &str, kind: Kind) -> Result<Element, ElementError> {
+        let text = text.trim();
+        let invalid = || ElementError::Invalid {
+            text: text.to_string(),
+            expected: Some(kind),
+        };
+        match kind {
+            Kind::Integer(integer_type) => literal::parse_as(text, integer_type)
+                .map(Element::Integer)
+                .map_err(ElementError::Literal),
+            Kind::Float => text.parse().map(Element::Float).map_err(|_| invalid()),
+            Kind::Text => match text
+                .strip_prefix('"')
+                .and_then(|text| text.strip_suffix('"'))
+            {
+                Some(inner) if !inner.contains('"') => Ok(Element::Text(inner.to_string())),
+                _ => Err(invalid()),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Element::Integer(value) => value.fmt(f),
+            Element::Float(value) => write!(f, "{value:?}"),
+            Element::Text(text) => write!(f, "{text:?}"),
+        }
+    }
+}
+
+macro_rules! element_from {
+    ($($type:ident => $variant:ident),*) => {
+        $(
+            impl From<$type> for Element {
+                fn from(value: $type) -> Element {
+                    Element::Integer(IntegerValue::$variant(value))
+                }
+            }
+        )*
+    };
+}
+
+element_from!(
+    i8 => I8, i16 => I16, i32 => I32, i64 => I64, i128 => I128,
+    u8 => U8, u16 => U16, u32 => U32, u64 => U64, u128 => U128
+);
+
+impl From<f64> for Element {
+    fn from(value: f64) -> Element {
+        Element::Float(value)
+    }
+}
+
+impl From<&str> for Element {
+    fn from(text: &str) -> Element {
+        Element::Text(text.to_string())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ElementError {
+    Literal(LiteralError),
+    /// Text that is not an element, or not one of the `expected` type.
+    Invalid {
+        text: String,
+        expected: Option<Kind>,
+    },
+}
+
+impl fmt::Display for ElementError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ElementError::Literal(error) => write!(f, "{error}"),
+            ElementError::Invalid {
+                text,
+                expected: Some(kind),
+            } => write!(f, "{text:?} is not a {kind}"),
+            ElementError::Invalid {
+                text,
+                expected: None,
+            } => write!(f, "{text:?} is not a number or a quoted string"),
+        }
+    }
+}
+
+impl Error for ElementError {}
+
+/// An array, whose elements share a type, or a tuple.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Compound {
+    Array(Vec<Element>),
+    Tuple(Vec<Element>),
+}
+
+impl Compound {
+    pub fn elements(&self) -> &[Element] {
+        match self {
+            Compound::Array(elements) | Compound::Tuple(elements) => elements,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements().is_empty()
+    }
+
+    /// The element at `index`, counting from the end if negative.
+    pub fn get(&self, index: isize) -> Option<&Element> {
+        self.elements().get(position(index, self.len())?)
+    }
+
+    pub fn get_mut(&mut self, index: isize) -> Option<&mut Element> {
+        let position = position(index, self.len())?;
+        match self {
+            Compound::Array(elements) | Compound::Tuple(elements) => elements.get_mut(position),
+        }
+    }
+}
+
+// The position of `index` in a compound of `len` elements, unless it
+// counts back past the first.
+fn position(index: isize, len: usize) -> Option<usize> {
+    if index < 0 {
+        len.checked_sub(index.unsigned_abs())
+    } else {
+        Some(index.unsigned_abs())
+    }
+}
+
+impl<T: Into<Element>, const N: usize> From<[T; N]> for Compound {
+    fn from(array: [T; N]) -> Compound {
+        Compound::Array(array.into_iter().map(Into::into).collect())
+    }
+}
+
+impl fmt::Display for Compound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let elements: Vec<String> = self.elements().iter().map(ToString::to_string).collect();
+        match self {
+            Compound::Array(_) => write!(f, "[{}]", elements.join(", ")),
+            Compound::Tuple(_) if elements.len() == 1 => write!(f, "({},)", elements[0]),
+            Compound::Tuple(_) => write!(f, "({})", elements.join(", ")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCompoundError {
+    /// Neither in square brackets nor in parentheses.
+    Unbracketed(String),
+    Element {
+        position: usize,
+        error: ElementError,
+    },
+    /// An array element of another type than the first.
+    Mixed {
+        position: usize,
+        expected: Kind,
+        found: Kind,
+    },
+}
+
+impl fmt::Display for ParseCompoundError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseCompoundError::Unbracketed(text) => {
+                write!(f, "expected [array] or (tuple), found {text:?}")
+            }
+            ParseCompoundError::Element { position, error } => {
+                write!(f, "element {position}: {error}")
+            }
+            ParseCompoundError::Mixed {
+                position,
+                expected,
+                found,
+            } => write!(f, "element {position}: expected {expected}, found {found}"),
+        }
+    }
+}
+
+impl Error for ParseCompoundError {}
+
+impl FromStr for Compound {
+    type Err = ParseCompoundError;
+
+    /// Parses `[1, 2, 3]` as an array and `(500, 6.4, "text")` as a tuple.
+    fn from_str(s: &str) -> Result<Compound, ParseCompoundError> {
+        let s = s.trim();
+        let (inner, array) =
+            if let Some(inner) = s.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+                (inner, true)
+            } else if let Some(inner) = s.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+                (inner, false)
+            } else {
+                return Err(ParseCompoundError::Unbracketed(s.to_string()));
+            };
+
+        // Array elements after the first take its type, as `[1u8, 2]` does.
+        let mut elements: Vec<Element> = Vec::new();
+        for (position, text) in split_elements(inner).into_iter().enumerate() {
+            let element_error = |error| ParseCompoundError::Element { position, error };
+            let Some(expected) = elements.first().filter(|_| array).map(Element::kind) else {
+                elements.push(Element::parse(text).map_err(element_error)?);
+                continue;
+            };
+            let element = match Element::parse_as(text, expected) {
+                Ok(element) => element,
+                Err(error) => match Element::parse(text).map_err(element_error)?.kind() {
+                    Kind::Integer(_) if matches!(expected, Kind::Integer(_)) => {
+                        return Err(element_error(error))
+                    }
+                    found => {
+                        return Err(ParseCompoundError::Mixed {
+                            position,
+                            expected,
+                            found,
+                        })
+                    }
+                },
+            };
+            elements.push(element);
+        }
+        Ok(if array {
+            Compound::Array(elements)
+        } else {
+            Compound::Tuple(elements)
+        })
+    }
+}
+
+// The comma separated parts of `text`, leaving commas in strings alone
+// and allowing a trailing comma.
+fn split_elements(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    for (i, c) in text.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                parts.push(&text[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    let last = &text[start..];
+    if !last.trim().is_empty() {
+        parts.push(last);
+    }
+    parts
+}
+
+/// A range of indices, as `1..3`, `..=2` or `-2..`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexRange {
+    pub start: Option<isize>,
+    pub end: Option<isize>,
+    pub inclusive: bool,
+}
+
+impl IndexRange {
+    /// The positions in a compound of `len` elements, if in bounds.
+    pub fn positions(self, len: usize) -> Option<std::ops::Range<usize>> {
+        let start = match self.start {
+            Some(index) => position(index, len)?,
+            None => 0,
+        };
+        let end = match self.end {
+            Some(index) if self.inclusive => position(index, len)?.checked_add(1)?,
+            Some(index) => position(index, len)?,
+            None => len,
+        };
+        (start <= end && end <= len).then_some(start..end)
+    }
+}
+
+impl fmt::Display for IndexRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(start) = self.start {
+            write!(f, "{start}")?;
+        }
+        f.write_str(if self.inclusive { "..=" } else { ".." })?;
+        if let Some(end) = self.end {
+            write!(f, "{end}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidRange(pub String);
+
+impl fmt::Display for InvalidRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} is not a range such as 1..3", self.0)
+    }
+}
+
+impl Error for InvalidRange {}
+
+impl FromStr for IndexRange {
+    type Err = InvalidRange;
+
+    fn from_str(s: &str) -> Result<IndexRange, InvalidRange> {
+        let invalid = || InvalidRange(s.to_string());
+        let (start, end, inclusive) = match s.split_once("..=") {
+            Some((start, end)) => (start, end, true),
+            None => {
+                let (start, end) = s.split_once("..").ok_or_else(invalid)?;
+                (start, end, false)
+            }
+        };
+        let bound = |text: &str| match text {
+            "" => Ok(None),
+            text => text.parse().map(Some).map_err(|_| invalid()),
+        };
+        let range = IndexRange {
+            start: bound(start)?,
+            end: bound(end)?,
+            inclusive,
+        };
+        if inclusive && range.end.is_none() {
+            return Err(invalid());
+        }
+        Ok(range)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndexError {
+    UnknownName(String),
+    OutOfBounds {
+        name: String,
+        index: isize,
+        len: usize,
+    },
+    RangeOutOfBounds {
+        name: String,
+        range: IndexRange,
+        len: usize,
+    },
+    /// Slicing a tuple, which, unlike an array, is not a slice.
+    NotAnArray(String),
+    /// Setting an element to one of another type.
+    Mismatch {
+        name: String,
+        index: isize,
+        expected: Kind,
+        found: Kind,
+    },
+}
+
+impl fmt::Display for IndexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IndexError::UnknownName(name) => write!(f, "no array or tuple named {name}"),
+            IndexError::OutOfBounds { name, index, len } => write!(
+                f,
+                "index {index} is out of bounds for {name}, of length {len}"
+            ),
+            IndexError::RangeOutOfBounds { name, range, len } => write!(
+                f,
+                "range {range} is out of bounds for {name}, of length {len}"
+            ),
+            IndexError::NotAnArray(name) => write!(f, "{name} is a tuple, which cannot be sliced"),
+            IndexError::Mismatch {
+                name,
+                index,
+                expected,
+                found,
+            } => write!(f, "{name}[{index}] is a {expected}, not a {found}"),
+        }
+    }
+}
+
+impl Error for IndexError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    UnknownCommand(String),
+    /// The arguments of a command, given as its form in `COMMANDS`.
+    Usage(&'static str),
+    InvalidName(String),
+    InvalidIndex(String),
+    InvalidRange(InvalidRange),
+    Element(ElementError),
+    Compound(ParseCompoundError),
+    Index(IndexError),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CommandError::UnknownCommand(command) => write!(f, "unknown command {command:?}"),
+            CommandError::Usage(form) => write!(f, "usage: {form}"),
+            CommandError::InvalidName(name) => write!(f, "{name:?} is not a valid name"),
+            CommandError::InvalidIndex(index) => write!(f, "{index:?} is not an index"),
+            CommandError::InvalidRange(error) => write!(f, "{error}"),
+            CommandError::Element(error) => write!(f, "{error}"),
+            CommandError::Compound(error) => write!(f, "{error}"),
+            CommandError::Index(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl Error for CommandError {}
+
+impl From<IndexError> for CommandError {
+    fn from(error: IndexError) -> CommandError {
+        CommandError::Index(error)
+    }
+}
+
+/// Arrays and tuples by name.
+#[derive(Debug, Clone, Default)]
+pub struct Scope {
+    bindings: BTreeMap<String, Compound>,
+}
+
+impl Scope {
+    pub fn new() -> Scope {
+        Scope::default()
+    }
+
+    /// Binds `compound` to `name`, returning what it shadows.
+    pub fn insert(&mut self, name: &str, compound: Compound) -> Option<Compound> {
+        self.bindings.insert(name.to_string(), compound)
+    }
+
+    pub fn compound(&self, name: &str) -> Result<&Compound, IndexError> {
+        self.bindings
+            .get(name)
+            .ok_or_else(|| IndexError::UnknownName(name.to_string()))
+    }
+
+    pub fn get(&self, name: &str, index: isize) -> Result<&Element, IndexError> {
+        let compound = self.compound(name)?;
+        compound.get(index).ok_or_else(|| IndexError::OutOfBounds {
+            name: name.to_string(),
+            index,
+            len: compound.len(),
+        })
+    }
+
+    /// Replaces the element at `index` with one of the same type,
+    /// returning the old one.
+    pub fn set(
+        &mut self,
+        name: &str,
+        index: isize,
+        element: Element,
+    ) -> Result<Element, IndexError> {
+        let compound = self
+            .bindings
+            .get_mut(name)
+            .ok_or_else(|| IndexError::UnknownName(name.to_string()))?;
+        let len = compound.len();
+        let slot = compound
+            .get_mut(index)
+            .ok_or_else(|| IndexError::OutOfBounds {
+                name: name.to_string(),
+                index,
+                len,
+            })?;
+        if slot.kind() != element.kind() {
+            return Err(IndexError::Mismatch {
+                name: name.to_string(),
+                index,
+                expected: slot.kind(),
+                found: element.kind(),
+            });
+        }
+        Ok(std::mem::replace(slot, element))
+    }
+
+    pub fn slice(&self, name: &str, range: IndexRange) -> Result<&[Element], IndexError> {
+        let Compound::Array(elements) = self.compound(name)? else {
+            return Err(IndexError::NotAnArray(name.to_string()));
+        };
+        range
+            .positions(elements.len())
+            .and_then(|positions| elements.get(positions))
+            .ok_or_else(|| IndexError::RangeOutOfBounds {
+                name: name.to_string(),
+                range,
+                len: elements.len(),
+            })
+    }
+
+    /// Runs one of `COMMANDS`, returning what it prints.
+    pub fn execute(&mut self, line: &str) -> Result<String, CommandError> {
+        let line = line.trim();
+        let (command, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let words: Vec<&str> = rest.split_whitespace().collect();
+        let index = |text: &str| {
+            text.parse::<isize>()
+                .map_err(|_| CommandError::InvalidIndex(text.to_string()))
+        };
+
+        match (command, words.as_slice()) {
+            ("get", [name, i]) => Ok(self.get(name, index(i)?)?.to_string()),
+            ("get", _) => Err(CommandError::Usage("get <name> <index>")),
+            ("set", [name, i, _, ..]) => {
+                let i = index(i)?;
+                // The element is the rest of the line, so may hold spaces.
+                let text = skip_words(rest, 2);
+                let kind = self.get(name, i)?.kind();
+                let element = Element::parse_as(text, kind).map_err(CommandError::Element)?;
+                self.set(name, i, element)?;
+                Ok(format!("{name} = {}", self.compound(name)?))
+            }
+            ("set", _) => Err(CommandError::Usage("set <name> <index> <element>")),
+            ("slice", [name, range]) => {
+                let range = range.parse().map_err(CommandError::InvalidRange)?;
+                let elements: Vec<String> = self
+                    .slice(name, range)?
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect();
+                Ok(format!("[{}]", elements.join(", ")))
+            }
+            ("slice", _) => Err(CommandError::Usage("slice <name> <range>")),
+            ("let", _) => {
+                let Some((name, compound)) = rest.split_once('=') else {
+                    return Err(CommandError::Usage("let <name> = <array|tuple>"));
+                };
+                let name = name.trim();
+                let mut chars = name.chars();
+                let valid = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+                    && chars.all(|c| c.is_alphanumeric() || c == '_');
+                if !valid {
+                    return Err(CommandError::InvalidName(name.to_string()));
+                }
+                let compound: Compound = compound.parse().map_err(CommandError::Compound)?;
+                let shown = format!("{name} = {compound}");
+                self.insert(name, compound);
+                Ok(shown)
+            }
+            ("show", []) => Ok(self
+                .bindings
+                .iter()
+                .map(|(name, compound)| format!("{name} = {compound}"))
+                .collect::<Vec<_>>()
+                .join("\n")),
+            ("show", [name]) => Ok(format!("{name} = {}", self.compound(name)?)),
+            ("show", _) => Err(CommandError::Usage("show [<name>]")),
+            ("help", _) => Ok(COMMANDS.to_string()),
+            (command, _) => Err(CommandError::UnknownCommand(command.to_string())),
+        }
+    }
+}
+
+// `text` without its first `count` words.
+fn skip_words(text: &str, count: usize) -> &str {
+    let mut text = text.trim_start();
+    for _ in 0..count {
+        text = text
+            .trim_start_matches(|c: char| !c.is_whitespace())
+            .trim_start();
+    }
+    text.trim_end()
+}
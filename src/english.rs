@@ -0,0 +1,134 @@
+// English Numbers
+// ///////////////
+//
+// Display adapters spelling out integers in English, for any `u64`:
+//
+//     Ordinal(22)        22nd
+//     Cardinal(103)      one hundred and three
+//     OrdinalWords(12)   twelfth
+//
+// Being `Display`, they go straight into `println!` and `format!`, width
+// and alignment included, and `to_string` gives the `String`.
+//
+// Numbers are written the British way, with "and" before the tens and
+// units of each group ("one hundred and three", "one thousand and one")
+// and short scale names for the groups of three digits (a billion is a
+// thousand millions).
+
+use std::fmt;
+
+const UNITS: [&str; 20] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+// u64::MAX is about 18.4 quintillion.
+const SCALES: [(u64, &str); 6] = [
+    (1_000_000_000_000_000_000, "quintillion"),
+    (1_000_000_000_000_000, "quadrillion"),
+    (1_000_000_000_000, "trillion"),
+    (1_000_000_000, "billion"),
+    (1_000_000, "million"),
+    (1_000, "thousand"),
+];
+
+/// A number with its ordinal suffix: 1st, 2nd, 3rd, 4th, 11th, 21st, ...
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ordinal(pub u64);
+
+impl fmt::Display for Ordinal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Ordinal(n) = *self;
+        let suffix = match (n % 10, n % 100) {
+            (_, 11..=13) => "th",
+            (1, _) => "st",
+            (2, _) => "nd",
+            (3, _) => "rd",
+            _ => "th",
+        };
+        f.pad(&format!("{n}{suffix}"))
+    }
+}
+
+/// A number in words: "forty-two", "one thousand and one".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cardinal(pub u64);
+
+impl fmt::Display for Cardinal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&cardinal_words(self.0))
+    }
+}
+
+/// An ordinal in words: "first", "forty-second", "one thousandth".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrdinalWords(pub u64);
+
+impl fmt::Display for OrdinalWords {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let words = cardinal_words(self.0);
+        // Only the last word changes: "twenty-one" becomes "twenty-first".
+        let split = words.rfind([' ', '-']).map_or(0, |index| index + 1);
+        let (head, last) = words.split_at(split);
+        let last = match last {
+            "one" => "first".to_string(),
+            "two" => "second".to_string(),
+            "three" => "third".to_string(),
+            "five" => "fifth".to_string(),
+            "eight" => "eighth".to_string(),
+            "nine" => "ninth".to_string(),
+            "twelve" => "twelfth".to_string(),
+            _ => match last.strip_suffix('y') {
+                Some(tens) => format!("{tens}ieth"),
+                None => format!("{last}th"),
+            },
+        };
+        f.pad(&format!("{head}{last}"))
+    }
+}
+
+fn cardinal_words(n: u64) -> String {
+    if n == 0 {
+        return UNITS[0].to_string();
+    }
+
+    let mut words = Vec::new();
+    let mut rest = n;
+    for (scale, name) in SCALES {
+        if rest >= scale {
+            words.push(format!("{} {name}", below_thousand(rest / scale)));
+            rest %= scale;
+        }
+    }
+    if rest > 0 {
+        // "one thousand and one", but "one thousand one hundred".
+        if !words.is_empty() && rest < 100 {
+            words.push("and".to_string());
+        }
+        words.push(below_thousand(rest));
+    }
+    words.join(" ")
+}
+
+// 1 to 999.
+fn below_thousand(n: u64) -> String {
+    let (hundreds, rest) = (n / 100, n % 100);
+    let rest = match rest {
+        0 => None,
+        1..=19 => Some(UNITS[rest as usize].to_string()),
+        _ => match (TENS[(rest / 10) as usize], rest % 10) {
+            (tens, 0) => Some(tens.to_string()),
+            (tens, units) => Some(format!("{tens}-{}", UNITS[units as usize])),
+        },
+    };
+    match (hundreds, rest) {
+        (0, Some(rest)) => rest,
+        (_, None) => format!("{} hundred", UNITS[hundreds as usize]),
+        (_, Some(rest)) => format!("{} hundred and {rest}", UNITS[hundreds as usize]),
+    }
+}
@@ -0,0 +1,266 @@
+// Fibonacci Numbers
+// /////////////////
+//
+// F(0) = 0, F(1) = 1 and F(n) = F(n - 1) + F(n - 2), computed in several
+// ways that all agree:
+//
+// * `recursive`, the textbook definition, which takes exponential time;
+// * `memoized`, the same recursion through a `memo::Memoized` cache, which
+//   computes each number once;
+// * `iterative`, n additions;
+// * `matrix`, raising [[1, 1], [1, 0]] to the nth power by squaring;
+// * `fast_doubling`, using F(2k) = F(k) * (2F(k + 1) - F(k)) and
+//   F(2k + 1) = F(k)^2 + F(k + 1)^2, which needs about log2(n) steps.
+//
+// F(93) is the largest Fibonacci number that fits a `u64` and F(186) the
+// largest that fits a `u128`. The plain functions panic past that, the
+// `checked_` ones return `None`, and `big` has no limit at all.
+//
+// Each strategy is written once, generic over the integer type, so the
+// same code runs on `u64`, `u128` and `BigUint`. `sequence` yields the
+// numbers one after the other instead; see `recurrence` for the general
+// case.
+
+use num_bigint::BigUint;
+use num_traits::{CheckedAdd, CheckedMul, CheckedSub, One, Zero};
+
+use crate::memo::{Memoized, Recurse};
+
+pub mod recurrence;
+
+use recurrence::Recurrence;
+
+/// Integer types the strategies can run on.
+pub trait FibonacciInt: Clone + Zero + One + CheckedAdd + CheckedSub + CheckedMul {}
+
+impl<T: Clone + Zero + One + CheckedAdd + CheckedSub + CheckedMul> FibonacciInt for T {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    Recursive,
+    Memoized,
+    Iterative,
+    Matrix,
+    FastDoubling,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 5] = [
+        Strategy::Recursive,
+        Strategy::Memoized,
+        Strategy::Iterative,
+        Strategy::Matrix,
+        Strategy::FastDoubling,
+    ];
+}
+
+/// F(n) by the given strategy, `None` if it overflows `T`.
+pub fn checked_with<T: FibonacciInt>(strategy: Strategy, n: u64) -> Option<T> {
+    match strategy {
+        Strategy::Recursive => recursive_in(n),
+        Strategy::Memoized => memoized_in(n),
+        Strategy::Iterative => iterative_in(n),
+        Strategy::Matrix => matrix_in(n),
+        Strategy::FastDoubling => fast_doubling_in(n),
+    }
+}
+
+/// The naive double recursion, kept for comparison: F(n) takes about
+/// F(n) calls.
+pub fn recursive(n: u64) -> u64 {
+    checked_recursive(n).expect("Fibonacci number overflows u64")
+}
+
+pub fn checked_recursive(n: u64) -> Option<u64> {
+    recursive_in(n)
+}
+
+/// The naive recursion with each F(k) cached the first time it is
+/// computed, so F(n) takes n calls.
+pub fn memoized(n: u64) -> u64 {
+    checked_memoized(n).expect("Fibonacci number overflows u64")
+}
+
+pub fn checked_memoized(n: u64) -> Option<u64> {
+    memoized_in(n)
+}
+
+pub fn iterative(n: u64) -> u64 {
+    checked_iterative(n).expect("Fibonacci number overflows u64")
+}
+
+pub fn checked_iterative(n: u64) -> Option<u64> {
+    iterative_in(n)
+}
+
+pub fn matrix(n: u64) -> u64 {
+    checked_matrix(n).expect("Fibonacci number overflows u64")
+}
+
+pub fn checked_matrix(n: u64) -> Option<u64> {
+    matrix_in(n)
+}
+
+pub fn fast_doubling(n: u64) -> u64 {
+    checked_fast_doubling(n).expect("Fibonacci number overflows u64")
+}
+
+pub fn checked_fast_doubling(n: u64) -> Option<u64> {
+    fast_doubling_in(n)
+}
+
+/// F(n) for n up to 186.
+pub fn checked_u128(n: u64) -> Option<u128> {
+    fast_doubling_in(n)
+}
+
+/// F(n) for any n, as an arbitrary-precision integer.
+pub fn big(n: u64) -> BigUint {
+    fast_doubling_in(n).expect("BigUint arithmetic cannot overflow")
+}
+
+/// F(0), F(1), F(2), ... for as long as they fit `T`.
+pub fn sequence<T: FibonacciInt>() -> Recurrence<T> {
+    Recurrence::fibonacci()
+}
+
+// The recursive strategies only notice an overflow on the way back up
+// from F(0): past the largest F(n) that fits `T`, the naive one would take
+// about F(n) calls and the memoized one, n nested calls deep, could
+// overflow the stack first. Fast doubling finds out in about log2(n)
+// steps.
+fn fits<T: FibonacciInt>(n: u64) -> bool {
+    fast_doubling_in::<T>(n).is_some()
+}
+
+fn recursive_in<T: FibonacciInt>(n: u64) -> Option<T> {
+    if !fits::<T>(n) {
+        return None;
+    }
+    naive::<T>(n)
+}
+
+fn naive<T: FibonacciInt>(n: u64) -> Option<T> {
+    match n {
+        0 => Some(T::zero()),
+        1 => Some(T::one()),
+        _ => naive::<T>(n - 2)?.checked_add(&naive(n - 1)?),
+    }
+}
+
+// Each call only looks back two numbers, so a small cache is enough: F(n)
+// first asks for F(n - 1), which leaves F(n - 2) among the three most
+// recently used entries.
+const MEMO_CAPACITY: usize = 8;
+
+fn memoized_in<T: FibonacciInt>(n: u64) -> Option<T> {
+    if !fits::<T>(n) {
+        return None;
+    }
+
+    let mut fibonacci = Memoized::new(
+        MEMO_CAPACITY,
+        |fibonacci: Recurse<u64, Option<T>>, n| match n {
+            0 => Some(T::zero()),
+            1 => Some(T::one()),
+            _ => fibonacci(n - 1)?.checked_add(&fibonacci(n - 2)?),
+        },
+    );
+    fibonacci.call(n)
+}
+
+// Stops one step short and returns F(n) as the sum of the last pair, so
+// that F(n + 1), which may not fit, is never computed.
+fn iterative_in<T: FibonacciInt>(n: u64) -> Option<T> {
+    if n == 0 {
+        return Some(T::zero());
+    }
+
+    let (mut a, mut b) = (T::zero(), T::one());
+    for _ in 1..n {
+        let next = a.checked_add(&b)?;
+        a = b;
+        b = next;
+    }
+    Some(b)
+}
+
+// [[1, 1], [1, 0]]^k = [[F(k + 1), F(k)], [F(k), F(k - 1)]], so F(n) is
+// the top-left entry of the (n - 1)th power.
+// The base is only squared while more bits remain, so no entry beyond
+// F(n) is ever computed.
+fn matrix_in<T: FibonacciInt>(n: u64) -> Option<T> {
+    if n == 0 {
+        return Some(T::zero());
+    }
+
+    let mut result = [[T::one(), T::zero()], [T::zero(), T::one()]];
+    let mut base = [[T::one(), T::one()], [T::one(), T::zero()]];
+    let mut exponent = n - 1;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = multiply(&result, &base)?;
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            base = multiply(&base, &base)?;
+        }
+    }
+
+    let [[top_left, _], _] = result;
+    Some(top_left)
+}
+
+type Matrix<T> = [[T; 2]; 2];
+
+fn multiply<T: FibonacciInt>(a: &Matrix<T>, b: &Matrix<T>) -> Option<Matrix<T>> {
+    let entry = |row: usize, column: usize| {
+        a[row][0]
+            .checked_mul(&b[0][column])?
+            .checked_add(&a[row][1].checked_mul(&b[1][column])?)
+    };
+    Some([[entry(0, 0)?, entry(0, 1)?], [entry(1, 0)?, entry(1, 1)?]])
+}
+
+// Only the last step is special: it computes F(n) alone, since F(n + 1)
+// may not fit.
+fn fast_doubling_in<T: FibonacciInt>(n: u64) -> Option<T> {
+    if n == 0 {
+        return Some(T::zero());
+    }
+
+    let (a, b) = doubling_pair::<T>(n / 2)?;
+    if n.is_multiple_of(2) {
+        double_even(&a, &b)
+    } else {
+        double_odd(&a, &b)
+    }
+}
+
+// (F(k), F(k + 1)).
+fn doubling_pair<T: FibonacciInt>(k: u64) -> Option<(T, T)> {
+    if k == 0 {
+        return Some((T::zero(), T::one()));
+    }
+
+    let (a, b) = doubling_pair::<T>(k / 2)?;
+    let even = double_even(&a, &b)?;
+    let odd = double_odd(&a, &b)?;
+    if k.is_multiple_of(2) {
+        Some((even, odd))
+    } else {
+        let next = even.checked_add(&odd)?;
+        Some((odd, next))
+    }
+}
+
+// F(2k) = F(k) * (2F(k + 1) - F(k)).
+fn double_even<T: FibonacciInt>(a: &T, b: &T) -> Option<T> {
+    a.checked_mul(&b.checked_add(b)?.checked_sub(a)?)
+}
+
+// F(2k + 1) = F(k)^2 + F(k + 1)^2.
+fn double_odd<T: FibonacciInt>(a: &T, b: &T) -> Option<T> {
+    a.checked_mul(a)?.checked_add(&b.checked_mul(b)?)
+}
@@ -0,0 +1,146 @@
+// Linear Recurrences
+// //////////////////
+//
+// A linear recurrence of order k defines each term as a fixed weighted sum
+// of the k terms before it:
+//
+//     a(n) = c1 * a(n - 1) + c2 * a(n - 2) + ... + ck * a(n - k)
+//
+// Fibonacci is the order 2 recurrence with coefficients [1, 1] and seeds
+// [0, 1]; Lucas, Tribonacci and Pell differ only in those two lists.
+//
+// `Recurrence` yields the terms lazily, computing each from the last k, so
+// taking the first n terms costs n steps rather than the n^2 of calling
+// `fibo_n` in a loop. It stops cleanly at the first term that overflows
+// `T`: every term before it is yielded, then the iterator returns `None`
+// for good.
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::iter::FusedIterator;
+
+use super::FibonacciInt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecurrenceError {
+    /// A recurrence needs at least one coefficient.
+    Empty,
+    /// There must be one seed per coefficient.
+    LengthMismatch { coefficients: usize, seeds: usize },
+}
+
+impl fmt::Display for RecurrenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RecurrenceError::Empty => write!(f, "a recurrence needs at least one coefficient"),
+            RecurrenceError::LengthMismatch {
+                coefficients,
+                seeds,
+            } => write!(
+                f,
+                "{coefficients} coefficients need {coefficients} seeds, got {seeds}"
+            ),
+        }
+    }
+}
+
+impl Error for RecurrenceError {}
+
+/// The terms of a linear recurrence, starting with its seeds.
+#[derive(Debug, Clone)]
+pub struct Recurrence<T> {
+    // The most recent term's coefficient first.
+    coefficients: Vec<T>,
+    // The terms still to be yielded, oldest first.
+    window: VecDeque<T>,
+    overflowed: bool,
+}
+
+impl<T: FibonacciInt> Recurrence<T> {
+    /// `coefficients[0]` weighs the previous term, `coefficients[1]` the
+    /// one before it, and so on. `seeds` are the first terms, in order.
+    pub fn new(coefficients: Vec<T>, seeds: Vec<T>) -> Result<Self, RecurrenceError> {
+        if coefficients.is_empty() {
+            return Err(RecurrenceError::Empty);
+        }
+        if coefficients.len() != seeds.len() {
+            return Err(RecurrenceError::LengthMismatch {
+                coefficients: coefficients.len(),
+                seeds: seeds.len(),
+            });
+        }
+
+        Ok(Recurrence {
+            coefficients,
+            window: seeds.into(),
+            overflowed: false,
+        })
+    }
+
+    /// 0, 1, 1, 2, 3, 5, 8, ...
+    pub fn fibonacci() -> Self {
+        Self::order_two([T::one(), T::one()], [T::zero(), T::one()])
+    }
+
+    /// 2, 1, 3, 4, 7, 11, 18, ...
+    pub fn lucas() -> Self {
+        Self::order_two([T::one(), T::one()], [T::one() + T::one(), T::one()])
+    }
+
+    /// 0, 1, 2, 5, 12, 29, 70, ...
+    pub fn pell() -> Self {
+        Self::order_two([T::one() + T::one(), T::one()], [T::zero(), T::one()])
+    }
+
+    /// 0, 0, 1, 1, 2, 4, 7, 13, ...
+    pub fn tribonacci() -> Self {
+        Recurrence {
+            coefficients: vec![T::one(), T::one(), T::one()],
+            window: [T::zero(), T::zero(), T::one()].into(),
+            overflowed: false,
+        }
+    }
+
+    fn order_two(coefficients: [T; 2], seeds: [T; 2]) -> Self {
+        Recurrence {
+            coefficients: coefficients.into(),
+            window: seeds.into(),
+            overflowed: false,
+        }
+    }
+
+    /// The term after the window, `None` if it overflows.
+    fn following(&self) -> Option<T> {
+        self.coefficients
+            .iter()
+            .zip(self.window.iter().rev())
+            .try_fold(T::zero(), |sum, (coefficient, term)| {
+                sum.checked_add(&coefficient.checked_mul(term)?)
+            })
+    }
+}
+
+impl<T: FibonacciInt> Iterator for Recurrence<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if !self.overflowed && !self.window.is_empty() {
+            match self.following() {
+                Some(term) => self.window.push_back(term),
+                None => self.overflowed = true,
+            }
+        }
+        self.window.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.overflowed {
+            (self.window.len(), Some(self.window.len()))
+        } else {
+            (self.window.len(), None)
+        }
+    }
+}
+
+impl<T: FibonacciInt> FusedIterator for Recurrence<T> {}
@@ -0,0 +1,248 @@
+// Guessing Game
+// /////////////
+//
+// The core of `src/bin/2_guessing_game.rs`, free of any I/O.
+//
+// A `Game` is a small state machine: every call to `submit` takes one
+// line of player input and answers with an `Outcome`, the same three-way
+// `Ordering` comparison as the book's version plus the ways a guess can
+// be rejected.
+// Frontends only have to read input, call `submit` and print the result.
+//
+// The word variant of `words` works the same way, and both implement
+// `GuessingGame`, so that one prompt loop can drive either.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use rand::Rng;
+
+pub mod hints;
+pub mod scores;
+pub mod server;
+pub mod solver;
+pub mod tui;
+pub mod words;
+
+/// A game played by submitting guesses, one line of input at a time, until
+/// it is over.
+pub trait GuessingGame {
+    /// The answer to a submitted guess, including why it was rejected.
+    type Feedback;
+
+    /// Takes one line of player input and answers it.
+    fn submit(&mut self, input: &str) -> Self::Feedback;
+    fn is_over(&self) -> bool;
+    fn is_won(&self) -> bool;
+    /// Number of valid guesses made so far.
+    fn attempts(&self) -> u32;
+}
+
+/// Answer of the game to a submitted guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The guess is smaller than the secret number.
+    Less,
+    /// The guess is bigger than the secret number.
+    Greater,
+    /// The guess is the secret number, the game is won.
+    Equal,
+    /// The input was rejected and did not count as an attempt.
+    Invalid(InvalidGuess),
+    /// The guess was wrong and no attempts are left, or the game was
+    /// already over.
+    OutOfAttempts,
+}
+
+/// Reason why an input was not accepted as a guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidGuess {
+    NotANumber,
+    OutOfRange,
+}
+
+/// Preset ranges and attempt limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Difficulty {
+    /// 1 to 10, unlimited guesses.
+    Easy,
+    /// 1 to 100, unlimited guesses, the book's original game.
+    Normal,
+    /// 1 to 1000, with just enough guesses for a perfect binary search.
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn range(self) -> RangeInclusive<u32> {
+        match self {
+            Difficulty::Easy => 1..=10,
+            Difficulty::Normal => 1..=100,
+            Difficulty::Hard => 1..=1000,
+        }
+    }
+
+    pub fn max_attempts(self) -> Option<u32> {
+        match self {
+            Difficulty::Easy | Difficulty::Normal => None,
+            Difficulty::Hard => Some(10),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Difficulty, String> {
+        Difficulty::ALL
+            .into_iter()
+            .find(|difficulty| difficulty.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown difficulty: {s} (expected easy, normal or hard)"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Playing,
+    Won,
+    Lost,
+}
+
+#[derive(Debug, Clone)]
+pub struct Game {
+    secret: u32,
+    range: RangeInclusive<u32>,
+    max_attempts: Option<u32>,
+    history: Vec<u32>,
+    status: Status,
+}
+
+impl Game {
+    /// Starts a game with a known secret, which must lie within `range`.
+    pub fn new(secret: u32, range: RangeInclusive<u32>) -> Game {
+        assert!(range.contains(&secret), "secret must lie within the range");
+        Game {
+            secret,
+            range,
+            max_attempts: None,
+            history: Vec::new(),
+            status: Status::Playing,
+        }
+    }
+
+    /// Starts a game whose secret is drawn from `rng`.
+    pub fn random(range: RangeInclusive<u32>, rng: &mut impl Rng) -> Game {
+        let secret = rng.gen_range(range.clone());
+        Game::new(secret, range)
+    }
+
+    /// Limits the number of valid guesses the player may make.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Game {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    pub fn secret(&self) -> u32 {
+        self.secret
+    }
+
+    pub fn range(&self) -> &RangeInclusive<u32> {
+        &self.range
+    }
+
+    pub fn max_attempts(&self) -> Option<u32> {
+        self.max_attempts
+    }
+
+    /// Number of valid guesses made so far.
+    pub fn attempts(&self) -> u32 {
+        self.history.len() as u32
+    }
+
+    /// Valid guesses in the order they were made.
+    pub fn history(&self) -> &[u32] {
+        &self.history
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.status == Status::Won
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.status != Status::Playing
+    }
+
+    /// Takes one line of player input and compares it with the secret.
+    pub fn submit(&mut self, input: &str) -> Outcome {
+        if self.is_over() {
+            return Outcome::OutOfAttempts;
+        }
+
+        // Parsing into a wider, signed type lets "-5" or "5000000000" be
+        // reported as out of range rather than as not being a number.
+        let guess: i64 = match input.trim().parse() {
+            Ok(num) => num,
+            Err(_) => return Outcome::Invalid(InvalidGuess::NotANumber),
+        };
+
+        let guess = match u32::try_from(guess) {
+            Ok(num) if self.range.contains(&num) => num,
+            _ => return Outcome::Invalid(InvalidGuess::OutOfRange),
+        };
+
+        self.history.push(guess);
+
+        let outcome = match guess.cmp(&self.secret) {
+            Ordering::Less => Outcome::Less,
+            Ordering::Greater => Outcome::Greater,
+            Ordering::Equal => {
+                self.status = Status::Won;
+                return Outcome::Equal;
+            }
+        };
+
+        if self.max_attempts.is_some_and(|max| self.attempts() >= max) {
+            self.status = Status::Lost;
+            return Outcome::OutOfAttempts;
+        }
+
+        outcome
+    }
+}
+
+impl GuessingGame for Game {
+    type Feedback = Outcome;
+
+    fn submit(&mut self, input: &str) -> Outcome {
+        Game::submit(self, input)
+    }
+
+    fn is_over(&self) -> bool {
+        Game::is_over(self)
+    }
+
+    fn is_won(&self) -> bool {
+        Game::is_won(self)
+    }
+
+    fn attempts(&self) -> u32 {
+        Game::attempts(self)
+    }
+}
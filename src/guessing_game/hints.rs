@@ -0,0 +1,138 @@
+// Hints
+// /////
+//
+// Optional feedback on top of "Too small!" and "Too big!": whether a
+// guess got closer to the secret than the previous one, and roughly how
+// far off it is.
+// Once a round is over, `narrowing_chart` draws how every answer shrank
+// the interval the secret could still be in, which is exactly what a
+// binary search does.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use super::solver::Solver;
+
+/// How a guess compares to the previous one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Hotter,
+    Colder,
+    Same,
+}
+
+impl fmt::Display for Trend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Trend::Hotter => "Hotter!",
+            Trend::Colder => "Colder!",
+            Trend::Same => "Neither hotter nor colder.",
+        })
+    }
+}
+
+/// Upper bounds of the distance buckets, the last bucket being anything
+/// beyond the biggest bound.
+pub const BUCKETS: [u32; 5] = [5, 20, 50, 100, 250];
+
+/// The smallest bucket that the distance between guess and secret fits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Distance {
+    Within(u32),
+    MoreThan(u32),
+}
+
+impl fmt::Display for Distance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Distance::Within(bound) => write!(f, "You are within {bound}."),
+            Distance::MoreThan(bound) => write!(f, "You are more than {bound} away."),
+        }
+    }
+}
+
+pub fn distance(guess: u32, secret: u32) -> Distance {
+    let off = guess.abs_diff(secret);
+    BUCKETS
+        .into_iter()
+        .find(|bound| off <= *bound)
+        .map_or(Distance::MoreThan(BUCKETS[BUCKETS.len() - 1]), Distance::Within)
+}
+
+pub fn trend(previous: u32, guess: u32, secret: u32) -> Trend {
+    let before = previous.abs_diff(secret);
+    let now = guess.abs_diff(secret);
+    match now.cmp(&before) {
+        Ordering::Less => Trend::Hotter,
+        Ordering::Greater => Trend::Colder,
+        Ordering::Equal => Trend::Same,
+    }
+}
+
+/// One line per guess: the guess, its verdict, a bar marking the part of
+/// `range` that was still possible afterwards, and that interval.
+pub fn narrowing_chart(
+    range: RangeInclusive<u32>,
+    history: &[u32],
+    secret: u32,
+    width: usize,
+) -> String {
+    let mut solver = Solver::new(range.clone());
+    let mut chart = String::new();
+
+    chart.push_str(&format!(
+        "{:>3}  {:>5}  {:<9}  |{}|  {}..={}\n",
+        "",
+        "",
+        "start",
+        interval_bar(&range, &range, width),
+        range.start(),
+        range.end()
+    ));
+
+    for (index, &guess) in history.iter().enumerate() {
+        let answer = guess.cmp(&secret);
+        // Every answer comes from the real secret, so it can never
+        // contradict an earlier one.
+        let _ = solver.answer(guess, answer);
+
+        let verdict = match answer {
+            Ordering::Less => "too small",
+            Ordering::Greater => "too big",
+            Ordering::Equal => "correct",
+        };
+        let remaining = solver.remaining();
+        chart.push_str(&format!(
+            "{:>3}. {guess:>5}  {verdict:<9}  |{}|  {}..={}\n",
+            index + 1,
+            interval_bar(&range, &remaining, width),
+            remaining.start(),
+            remaining.end()
+        ));
+    }
+
+    chart
+}
+
+/// A bar of `width` cells, each covering an equal share of `range`, which
+/// is filled if any of its numbers lies within `remaining`.
+pub fn interval_bar(
+    range: &RangeInclusive<u32>,
+    remaining: &RangeInclusive<u32>,
+    width: usize,
+) -> String {
+    let start = u64::from(*range.start());
+    let size = u64::from(*range.end()) - start + 1;
+    let width = width.max(1) as u64;
+
+    (0..width)
+        .map(|cell| {
+            let first = start + cell * size / width;
+            let last = (start + (cell + 1) * size / width).max(first + 1) - 1;
+            let filled = first <= u64::from(*remaining.end())
+                && last >= u64::from(*remaining.start());
+            if filled { '#' } else { '.' }
+        })
+        .collect()
+}
@@ -0,0 +1,177 @@
+// High Scores
+// ///////////
+//
+// Every win is appended as one JSON object per line to a score file, by
+// default `rust_book/guessing_game_scores.jsonl` under the user's data
+// directory.
+// Scores of the classic game, the countdown and the speedrun modes share
+// the file but are ranked separately.
+// Appending, rather than rewriting a whole JSON document, keeps earlier
+// scores intact even if two games finish at the same time.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+// Overrides the location of the score file, mostly useful for tests.
+pub const SCORES_ENV_VAR: &str = "GUESSING_GAME_SCORES";
+
+/// The way a game was played.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Mode {
+    /// One round without a time limit.
+    #[default]
+    Classic,
+    /// One round to be won within `seconds`.
+    Countdown { seconds: u64 },
+    /// `rounds` rounds in a row, against the clock.
+    Speedrun { rounds: u32 },
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Mode::Classic => write!(f, "classic"),
+            Mode::Countdown { seconds } => write!(f, "countdown {seconds}s"),
+            Mode::Speedrun { rounds } => write!(f, "speedrun {rounds} rounds"),
+        }
+    }
+}
+
+/// One won game, or one completed speedrun.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Score {
+    pub player: String,
+    /// Scores recorded before modes existed are classic ones.
+    #[serde(default)]
+    pub mode: Mode,
+    /// Name of the difficulty preset, or "custom" for a hand-picked range.
+    pub difficulty: String,
+    /// Attempts of all rounds together for a speedrun.
+    pub attempts: u32,
+    pub elapsed_ms: u64,
+    /// Seconds since the Unix epoch at the time of the win.
+    pub timestamp: u64,
+}
+
+impl Score {
+    /// The date of the win as `YYYY-MM-DD` (UTC).
+    pub fn date(&self) -> String {
+        let (year, month, day) = civil_from_days((self.timestamp / 86_400) as i64);
+        format!("{year:04}-{month:02}-{day:02}")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScoreBoard {
+    path: PathBuf,
+}
+
+impl ScoreBoard {
+    pub fn new(path: impl Into<PathBuf>) -> ScoreBoard {
+        ScoreBoard { path: path.into() }
+    }
+
+    /// Opens the score file named by `GUESSING_GAME_SCORES`, falling back
+    /// to the user's data directory.
+    pub fn open_default() -> Option<ScoreBoard> {
+        let path = match env::var_os(SCORES_ENV_VAR) {
+            Some(path) => PathBuf::from(path),
+            None => data_dir()?.join("rust_book").join("guessing_game_scores.jsonl"),
+        };
+        Some(ScoreBoard::new(path))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn record(&self, score: &Score) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        let line = serde_json::to_string(score)?;
+        writeln!(file, "{line}")
+    }
+
+    /// Reads every recorded score, a missing file being an empty table.
+    /// Lines that cannot be parsed are skipped rather than failing the
+    /// whole table.
+    pub fn load(&self) -> io::Result<Vec<Score>> {
+        let file = match fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+
+        let mut scores = Vec::new();
+        for line in BufReader::new(file).lines() {
+            if let Ok(score) = serde_json::from_str(&line?) {
+                scores.push(score);
+            }
+        }
+        Ok(scores)
+    }
+
+    /// The best `n` scores of each mode and difficulty.
+    /// Speedruns rank the fastest first, then the fewest attempts, every
+    /// other mode the other way round; ties go to the earliest score.
+    pub fn top(&self, n: usize) -> io::Result<BTreeMap<(Mode, String), Vec<Score>>> {
+        let mut table: BTreeMap<(Mode, String), Vec<Score>> = BTreeMap::new();
+        for score in self.load()? {
+            table
+                .entry((score.mode, score.difficulty.clone()))
+                .or_default()
+                .push(score);
+        }
+
+        for ((mode, _), scores) in table.iter_mut() {
+            match mode {
+                Mode::Speedrun { .. } => scores
+                    .sort_by_key(|score| (score.elapsed_ms, score.attempts, score.timestamp)),
+                Mode::Classic | Mode::Countdown { .. } => scores
+                    .sort_by_key(|score| (score.attempts, score.elapsed_ms, score.timestamp)),
+            }
+            scores.truncate(n);
+        }
+        Ok(table)
+    }
+}
+
+// `$XDG_DATA_HOME`, `~/.local/share` or `%APPDATA%`, whichever is set.
+fn data_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("XDG_DATA_HOME").filter(|dir| !dir.is_empty()) {
+        return Some(PathBuf::from(dir));
+    }
+    if let Some(home) = env::var_os("HOME").filter(|home| !home.is_empty()) {
+        return Some(PathBuf::from(home).join(".local").join("share"));
+    }
+    env::var_os("APPDATA").map(PathBuf::from)
+}
+
+// Converts days since 1970-01-01 into a (year, month, day) triple,
+// following Howard Hinnant's `civil_from_days` algorithm.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
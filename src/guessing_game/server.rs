@@ -0,0 +1,314 @@
+// Multiplayer Server
+// //////////////////
+//
+// Runs the guessing game for several players over TCP.
+// Players join a named room, each room having its own secret number, and
+// take turns guessing in the order they joined.
+// Every result is broadcast to the whole room, and the first correct
+// guess wins the round, after which a new round starts straight away.
+//
+// The protocol is line based so that `nc localhost 7878` is a usable
+// client.
+// Client to server:
+//
+//     JOIN <room> <name>
+//     GUESS <number>
+//     QUIT
+//
+// Server to client:
+//
+//     WELCOME <room> <round> <min> <max>
+//     JOINED <name>
+//     LEFT <name>
+//     TURN <name>
+//     RESULT <name> <number> TOO_SMALL|TOO_BIG
+//     WIN <name> <number> <attempts>
+//     ROUND <round> <min> <max>
+//     ERROR <message>
+//     BYE
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::ops::RangeInclusive;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use super::{Game, InvalidGuess, Outcome};
+
+pub struct Server {
+    range: RangeInclusive<u32>,
+    state: Mutex<State>,
+}
+
+struct State {
+    rng: StdRng,
+    rooms: HashMap<String, Room>,
+    next_id: u64,
+}
+
+struct Room {
+    game: Game,
+    round: u32,
+    players: Vec<Player>,
+    // Index into `players` of the player whose turn it is.
+    turn: usize,
+}
+
+struct Player {
+    id: u64,
+    name: String,
+    stream: Arc<TcpStream>,
+}
+
+// Where a connection stands once it has joined a room.
+struct Seat {
+    room: String,
+    id: u64,
+}
+
+// Messages collected while the state is locked and sent once it is
+// released, so that a client that stops reading only holds up the thread
+// writing to it, rather than every room.
+#[derive(Default)]
+struct Outbox {
+    messages: Vec<(Arc<TcpStream>, String)>,
+}
+
+impl Outbox {
+    fn push(&mut self, stream: &Arc<TcpStream>, message: String) {
+        self.messages.push((Arc::clone(stream), message));
+    }
+
+    fn send(self) {
+        for (stream, message) in self.messages {
+            // A player whose connection has failed is removed by its own
+            // connection thread, so there is nothing to do here.
+            let _ = writeln!(&*stream, "{message}");
+        }
+    }
+}
+
+impl Room {
+    fn broadcast(&self, outbox: &mut Outbox, message: &str) {
+        for player in &self.players {
+            outbox.push(&player.stream, message.to_string());
+        }
+    }
+
+    fn announce_turn(&self, outbox: &mut Outbox) {
+        if let Some(player) = self.players.get(self.turn) {
+            self.broadcast(outbox, &format!("TURN {}", player.name));
+        }
+    }
+}
+
+impl Server {
+    /// Every room draws its secret numbers from `range`, using one random
+    /// number generator seeded with `seed`.
+    pub fn new(range: RangeInclusive<u32>, seed: u64) -> Server {
+        Server {
+            range,
+            state: Mutex::new(State {
+                rng: StdRng::seed_from_u64(seed),
+                rooms: HashMap::new(),
+                next_id: 0,
+            }),
+        }
+    }
+
+    /// Accepts connections forever, one thread per player.
+    pub fn serve(self: Arc<Self>, listener: TcpListener) -> io::Result<()> {
+        for stream in listener.incoming() {
+            let stream = stream?;
+            let server = Arc::clone(&self);
+            thread::spawn(move || {
+                if let Err(err) = server.handle(stream) {
+                    eprintln!("Connection closed with an error: {err}");
+                }
+            });
+        }
+        Ok(())
+    }
+
+    fn handle(&self, stream: TcpStream) -> io::Result<()> {
+        let mut seat: Option<Seat> = None;
+        let result = self.converse(&stream, &mut seat);
+
+        // Whichever way the conversation ended, an I/O error included, the
+        // player leaves, so that the room does not wait on their turn.
+        if let Some(seat) = seat {
+            self.leave(&seat);
+        }
+        result
+    }
+
+    fn converse(&self, stream: &TcpStream, seat: &mut Option<Seat>) -> io::Result<()> {
+        let reader = BufReader::new(stream.try_clone()?);
+        let mut writer = stream;
+
+        for line in reader.lines() {
+            let line = line?;
+            let mut words = line.split_whitespace();
+
+            match (words.next(), &*seat) {
+                (None, _) => continue,
+                (Some("JOIN"), None) => match (words.next(), words.next(), words.next()) {
+                    (Some(room), Some(name), None) => {
+                        match self.join(room, name, stream.try_clone()?) {
+                            Ok(joined) => *seat = Some(joined),
+                            Err(message) => writeln!(writer, "ERROR {message}")?,
+                        }
+                    }
+                    _ => writeln!(writer, "ERROR usage: JOIN <room> <name>")?,
+                },
+                (Some("JOIN"), Some(_)) => writeln!(writer, "ERROR already in a room")?,
+                (Some("GUESS"), Some(seat)) => match (words.next(), words.next()) {
+                    (Some(guess), None) => {
+                        if let Err(message) = self.guess(seat, guess) {
+                            writeln!(writer, "ERROR {message}")?;
+                        }
+                    }
+                    _ => writeln!(writer, "ERROR usage: GUESS <number>")?,
+                },
+                (Some("GUESS"), None) => writeln!(writer, "ERROR join a room first")?,
+                (Some("QUIT"), _) => {
+                    writeln!(writer, "BYE")?;
+                    break;
+                }
+                (Some(command), _) => writeln!(writer, "ERROR unknown command: {command}")?,
+            }
+        }
+        Ok(())
+    }
+
+    // Runs `f` on the locked state, then sends what it put in the outbox.
+    fn with_state<T>(&self, f: impl FnOnce(&mut State, &mut Outbox) -> T) -> T {
+        let mut outbox = Outbox::default();
+        let result = f(&mut self.state.lock().unwrap(), &mut outbox);
+        outbox.send();
+        result
+    }
+
+    fn join(&self, room_name: &str, name: &str, stream: TcpStream) -> Result<Seat, String> {
+        self.with_state(|state, outbox| {
+            let State { rng, rooms, next_id } = state;
+
+            let room = rooms.entry(room_name.to_string()).or_insert_with(|| Room {
+                game: Game::random(self.range.clone(), rng),
+                round: 1,
+                players: Vec::new(),
+                turn: 0,
+            });
+
+            if room.players.iter().any(|player| player.name == name) {
+                return Err(format!("name already taken: {name}"));
+            }
+
+            let id = *next_id;
+            *next_id += 1;
+
+            let stream = Arc::new(stream);
+            let welcome = format!(
+                "WELCOME {room_name} {} {} {}",
+                room.round,
+                self.range.start(),
+                self.range.end()
+            );
+            outbox.push(&stream, welcome);
+            room.broadcast(outbox, &format!("JOINED {name}"));
+            room.players.push(Player { id, name: name.to_string(), stream });
+            room.announce_turn(outbox);
+
+            Ok(Seat { room: room_name.to_string(), id })
+        })
+    }
+
+    // Errors are for the guesser alone.
+    fn guess(&self, seat: &Seat, guess: &str) -> Result<(), String> {
+        self.with_state(|state, outbox| {
+            let State { rng, rooms, .. } = state;
+            let Some(room) = rooms.get_mut(&seat.room) else {
+                return Ok(());
+            };
+
+            if room.players[room.turn].id != seat.id {
+                return Err("not your turn".to_string());
+            }
+
+            let outcome = room.game.submit(guess);
+            let name = room.players[room.turn].name.clone();
+            let number = room.game.history().last().copied().unwrap_or_default();
+
+            match outcome {
+                Outcome::Invalid(InvalidGuess::NotANumber) => {
+                    return Err(format!("not a number: {guess}"));
+                }
+                Outcome::Invalid(InvalidGuess::OutOfRange) => {
+                    let range = room.game.range();
+                    return Err(format!("out of range {} {}", range.start(), range.end()));
+                }
+                Outcome::Less => {
+                    room.broadcast(outbox, &format!("RESULT {name} {number} TOO_SMALL"));
+                }
+                Outcome::Greater => {
+                    room.broadcast(outbox, &format!("RESULT {name} {number} TOO_BIG"));
+                }
+                // Rooms have no attempt limit, so a round can only end in a win.
+                Outcome::Equal | Outcome::OutOfAttempts => {
+                    let win = format!("WIN {name} {number} {}", room.game.attempts());
+                    room.broadcast(outbox, &win);
+
+                    room.round += 1;
+                    room.game = Game::random(self.range.clone(), rng);
+                    let message = format!(
+                        "ROUND {} {} {}",
+                        room.round,
+                        self.range.start(),
+                        self.range.end()
+                    );
+                    room.broadcast(outbox, &message);
+                }
+            }
+
+            room.turn = (room.turn + 1) % room.players.len();
+            room.announce_turn(outbox);
+            Ok(())
+        })
+    }
+
+    fn leave(&self, seat: &Seat) {
+        self.with_state(|state, outbox| {
+            let Some(room) = state.rooms.get_mut(&seat.room) else {
+                return;
+            };
+            let Some(index) = room.players.iter().position(|player| player.id == seat.id) else {
+                return;
+            };
+
+            let player = room.players.remove(index);
+            if room.players.is_empty() {
+                state.rooms.remove(&seat.room);
+                return;
+            }
+
+            room.broadcast(outbox, &format!("LEFT {}", player.name));
+
+            // Everyone keeps their place in the rotation, and if it was the
+            // departed player's turn it passes on to the next one.
+            let had_turn = index == room.turn;
+            if index < room.turn {
+                room.turn -= 1;
+            }
+            if room.turn >= room.players.len() {
+                room.turn = 0;
+            }
+            if had_turn {
+                room.announce_turn(outbox);
+            }
+        })
+    }
+}
@@ -0,0 +1,158 @@
+// Solver
+// //////
+//
+// The guessing game with the roles reversed: the player thinks of a
+// number and the program guesses it, told after every guess whether it
+// was too small or too big - the same `Ordering` the game itself uses.
+//
+// Guessing the middle of the numbers still possible halves the interval
+// with every answer, so no number takes more than `worst_case` guesses.
+// Because the solver keeps track of which answer set each bound, it can
+// also point out a player who contradicts an earlier answer.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+#[derive(Debug, Clone)]
+pub struct Solver {
+    range: RangeInclusive<u32>,
+    low: u32,
+    high: u32,
+    // The guesses that were answered "too small" and "too big" and so
+    // moved `low` and `high` to where they are now.
+    low_from: Option<u32>,
+    high_from: Option<u32>,
+    guesses: u32,
+    solved: bool,
+}
+
+/// An answer that cannot be true given the earlier ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contradiction {
+    pub guess: u32,
+    pub answer: Ordering,
+    /// The earlier answer it clashes with, or `None` if it clashes with
+    /// the range itself.
+    pub earlier: Option<(u32, Ordering)>,
+    pub range: (u32, u32),
+}
+
+impl fmt::Display for Contradiction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.earlier {
+            Some((earlier, answer)) => write!(
+                f,
+                "you said {earlier} was {}, now {} is {}",
+                describe(answer),
+                self.guess,
+                describe(self.answer)
+            ),
+            None => write!(
+                f,
+                "{} cannot be {}, the number is between {} and {}",
+                self.guess,
+                describe(self.answer),
+                self.range.0,
+                self.range.1
+            ),
+        }
+    }
+}
+
+fn describe(answer: Ordering) -> &'static str {
+    match answer {
+        Ordering::Less => "too small",
+        Ordering::Greater => "too big",
+        Ordering::Equal => "correct",
+    }
+}
+
+impl Solver {
+    pub fn new(range: RangeInclusive<u32>) -> Solver {
+        Solver {
+            low: *range.start(),
+            high: *range.end(),
+            range,
+            low_from: None,
+            high_from: None,
+            guesses: 0,
+            solved: false,
+        }
+    }
+
+    /// The most guesses binary search needs for any number in the range,
+    /// ceil(log2(n + 1)) for n candidates.
+    /// For the 1 to 100 range that is 7.
+    pub fn worst_case(&self) -> u32 {
+        let candidates = u64::from(self.range.end() - self.range.start()) + 1;
+        u64::BITS - candidates.leading_zeros()
+    }
+
+    /// The middle of the numbers that are still possible.
+    pub fn next_guess(&self) -> u32 {
+        self.low + (self.high - self.low) / 2
+    }
+
+    /// The numbers that are still possible.
+    pub fn remaining(&self) -> RangeInclusive<u32> {
+        self.low..=self.high
+    }
+
+    pub fn guesses(&self) -> u32 {
+        self.guesses
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.solved
+    }
+
+    /// Records the player's answer about `guess`, as in
+    /// `guess.cmp(&secret_number)`.
+    /// An answer that contradicts the earlier ones is rejected and leaves
+    /// the solver unchanged.
+    pub fn answer(&mut self, guess: u32, answer: Ordering) -> Result<(), Contradiction> {
+        let too_small = self.low_from.map(|g| (g, Ordering::Less));
+        let too_big = self.high_from.map(|g| (g, Ordering::Greater));
+
+        let earlier = match answer {
+            // The number would have to be bigger than `guess`.
+            Ordering::Less if guess >= self.high => Some(too_big),
+            // The number would have to be smaller than `guess`.
+            Ordering::Greater if guess <= self.low => Some(too_small),
+            Ordering::Equal if guess < self.low => Some(too_small),
+            Ordering::Equal if guess > self.high => Some(too_big),
+            _ => None,
+        };
+
+        if let Some(earlier) = earlier {
+            return Err(Contradiction {
+                guess,
+                answer,
+                earlier,
+                range: (*self.range.start(), *self.range.end()),
+            });
+        }
+
+        self.guesses += 1;
+        match answer {
+            // Answers about guesses outside the remaining interval are
+            // consistent but tell us nothing new.
+            Ordering::Less if guess >= self.low => {
+                self.low = guess + 1;
+                self.low_from = Some(guess);
+            }
+            Ordering::Greater if guess <= self.high => {
+                self.high = guess - 1;
+                self.high_from = Some(guess);
+            }
+            Ordering::Less | Ordering::Greater => {}
+            Ordering::Equal => {
+                self.low = guess;
+                self.high = guess;
+                self.solved = true;
+            }
+        }
+        Ok(())
+    }
+}
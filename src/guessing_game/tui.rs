@@ -0,0 +1,249 @@
+// Terminal User Interface
+// ///////////////////////
+//
+// A full-screen frontend for `Game`, drawn with `crossterm`:
+//
+//     Guess the number!                   Attempts: 3/10   Time: 00:12
+//
+//     1 |..........##########..........................| 1000
+//     The secret number is between 251 and 599.
+//
+//     Past guesses:
+//       3.   600  Too big!
+//       2.   250  Too small!
+//       1.   500  Too big!
+//
+//     Too big!
+//     > 42
+//
+// The number line is the interval the secret can still be in, worked out
+// by the same `Solver` the solver mode uses.
+// The screen is redrawn a few times per second so that the timer keeps
+// running while the player thinks.
+//
+// The terminal needs raw mode for this, so `run` should only be called
+// when stdin and stdout are both a terminal.
+
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::terminal::{self, ClearType};
+use crossterm::{cursor, execute, queue};
+
+use super::hints;
+use super::solver::Solver;
+use super::{Game, InvalidGuess, Outcome};
+
+// Longest input accepted, enough for any `u32` and a sign.
+const MAX_INPUT: usize = 11;
+
+struct Screen {
+    input: String,
+    message: String,
+    solver: Solver,
+    // Every valid guess with the feedback it got, oldest first.
+    results: Vec<(u32, &'static str)>,
+    started: Instant,
+    finished: Option<Duration>,
+}
+
+/// Plays `game` until it is over or the player presses Esc.
+/// The terminal is restored before returning, even on error or panic.
+pub fn run(game: &mut Game, started: Instant) -> io::Result<()> {
+    let mut stdout = io::stdout();
+
+    let raw_mode = RawMode::enable()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let result = event_loop(game, started, &mut stdout);
+
+    raw_mode.restore()?;
+    result
+}
+
+// Leaves the alternate screen and raw mode when dropped, so that the
+// terminal is usable again however `run` exits. `restore` does the same
+// but reports errors.
+struct RawMode {
+    restored: bool,
+}
+
+impl RawMode {
+    fn enable() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        Ok(RawMode { restored: false })
+    }
+
+    fn restore(mut self) -> io::Result<()> {
+        self.restored = true;
+        leave_terminal()
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        if !self.restored {
+            let _ = leave_terminal();
+        }
+    }
+}
+
+// Raw mode is left even if the alternate screen could not be.
+fn leave_terminal() -> io::Result<()> {
+    let screen = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+    let raw_mode = terminal::disable_raw_mode();
+    screen.and(raw_mode)
+}
+
+fn event_loop(game: &mut Game, started: Instant, out: &mut impl Write) -> io::Result<()> {
+    let mut screen = Screen {
+        input: String::new(),
+        message: String::from("Type a number and press Enter."),
+        solver: Solver::new(game.range().clone()),
+        results: Vec::new(),
+        started,
+        finished: None,
+    };
+
+    loop {
+        draw(game, &screen, out)?;
+
+        if !event::poll(Duration::from_millis(250))? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        // Once the game is over any key leaves the screen.
+        if game.is_over() {
+            return Ok(());
+        }
+
+        match key.code {
+            KeyCode::Esc => return Ok(()),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Ok(());
+            }
+            KeyCode::Char(c)
+                if (c.is_ascii_digit() || c == '-') && screen.input.len() < MAX_INPUT =>
+            {
+                screen.input.push(c);
+            }
+            KeyCode::Backspace => {
+                screen.input.pop();
+            }
+            KeyCode::Enter => submit(game, &mut screen),
+            _ => {}
+        }
+    }
+}
+
+fn submit(game: &mut Game, screen: &mut Screen) {
+    let outcome = game.submit(&screen.input);
+    screen.input.clear();
+
+    let guess = game.history().last().copied();
+    let feedback = match outcome {
+        Outcome::Invalid(InvalidGuess::NotANumber) => {
+            screen.message = String::from("Please type a number!");
+            return;
+        }
+        Outcome::Invalid(InvalidGuess::OutOfRange) => {
+            screen.message = format!(
+                "Out of range! The secret number is between {} and {}.",
+                game.range().start(),
+                game.range().end()
+            );
+            return;
+        }
+        Outcome::Less => "Too small!",
+        Outcome::Greater => "Too big!",
+        Outcome::Equal => "You win!",
+        Outcome::OutOfAttempts => "Out of attempts!",
+    };
+
+    if let Some(guess) = guess {
+        // The answers come from the real secret, so they never contradict
+        // each other.
+        let _ = screen.solver.answer(guess, guess.cmp(&game.secret()));
+        screen.results.push((guess, feedback));
+    }
+
+    screen.message = if game.is_won() {
+        format!("You win in {} attempts! Press any key.", game.attempts())
+    } else if game.is_over() {
+        format!("Out of attempts! The secret number was {}. Press any key.", game.secret())
+    } else {
+        String::from(feedback)
+    };
+
+    if game.is_over() {
+        screen.finished = Some(screen.started.elapsed());
+    }
+}
+
+fn draw(game: &Game, screen: &Screen, out: &mut impl Write) -> io::Result<()> {
+    let (columns, rows) = terminal::size()?;
+    let (columns, rows) = (usize::from(columns), rows);
+
+    queue!(out, terminal::Clear(ClearType::All))?;
+
+    let elapsed = screen.finished.unwrap_or_else(|| screen.started.elapsed()).as_secs();
+    let attempts = match game.max_attempts() {
+        Some(max) => format!("{}/{max}", game.attempts()),
+        None => game.attempts().to_string(),
+    };
+    let status = format!(
+        "Attempts: {attempts}   Time: {:02}:{:02}",
+        elapsed / 60,
+        elapsed % 60
+    );
+    let title = "Guess the number!";
+    let gap = columns.saturating_sub(title.len() + status.len()).max(1);
+    line(out, 0, &format!("{title}{}{status}", " ".repeat(gap)))?;
+
+    let range = game.range();
+    let remaining = screen.solver.remaining();
+    let (start, end) = (range.start().to_string(), range.end().to_string());
+    let width = columns.saturating_sub(start.len() + end.len() + 4).max(1);
+    let bar = hints::interval_bar(range, &remaining, width);
+    line(out, 2, &format!("{start} |{bar}| {end}"))?;
+    line(
+        out,
+        3,
+        &format!(
+            "The secret number is between {} and {}.",
+            remaining.start(),
+            remaining.end()
+        ),
+    )?;
+
+    line(out, 5, "Past guesses:")?;
+    // The list shows the newest guesses first and keeps whatever fits
+    // between the header and the prompt.
+    let visible = usize::from(rows.saturating_sub(10));
+    for (row, (index, (guess, feedback))) in
+        screen.results.iter().enumerate().rev().take(visible).enumerate()
+    {
+        line(out, 6 + row as u16, &format!("{:>3}. {guess:>5}  {feedback}", index + 1))?;
+    }
+
+    let bottom = rows.saturating_sub(1);
+    line(out, bottom.saturating_sub(2), &screen.message)?;
+    if !game.is_over() {
+        line(out, bottom.saturating_sub(1), &format!("> {}", screen.input))?;
+        line(out, bottom, "Enter: guess   Backspace: erase   Esc: quit")?;
+    }
+
+    out.flush()
+}
+
+fn line(out: &mut impl Write, row: u16, text: &str) -> io::Result<()> {
+    queue!(out, cursor::MoveTo(0, row), Print(text))
+}
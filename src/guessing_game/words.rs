@@ -0,0 +1,229 @@
+// Word Game
+// /////////
+//
+// The guessing game with words instead of numbers, in the style of
+// Wordle: the player has six attempts to find a secret word from a word
+// list, and every guess is answered letter by letter.
+//
+// Letters are compared as `char`s, that is as Unicode scalar values, so
+// words such as "été" work as expected as long as the word list and the
+// player use the same (usually precomposed) form of each accented letter.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use rand::Rng;
+
+use super::GuessingGame;
+
+pub const MAX_ATTEMPTS: u32 = 6;
+
+/// The words that may be picked as a secret and accepted as guesses.
+#[derive(Debug, Clone)]
+pub struct Dictionary {
+    words: Vec<String>,
+    known: HashSet<String>,
+}
+
+impl Dictionary {
+    /// Builds a dictionary from words that are trimmed and lowercased.
+    /// Empty words are dropped.
+    pub fn new<I, S>(words: I) -> Dictionary
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut dictionary = Dictionary { words: Vec::new(), known: HashSet::new() };
+        for word in words {
+            let word = word.as_ref().trim().to_lowercase();
+            if !word.is_empty() && dictionary.known.insert(word.clone()) {
+                dictionary.words.push(word);
+            }
+        }
+        dictionary
+    }
+
+    /// Reads a word list with one word per line, lines starting with `#`
+    /// being comments.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Dictionary> {
+        let contents = fs::read_to_string(path)?;
+        Ok(Dictionary::new(
+            contents.lines().filter(|line| !line.trim_start().starts_with('#')),
+        ))
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.known.contains(word)
+    }
+
+    /// Picks a random word, `None` if the dictionary is empty.
+    pub fn random(&self, rng: &mut impl Rng) -> Option<&str> {
+        if self.words.is_empty() {
+            return None;
+        }
+        Some(&self.words[rng.gen_range(0..self.words.len())])
+    }
+}
+
+/// What a letter of a guess reveals about the secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mark {
+    /// The secret has this letter at this position.
+    Correct,
+    /// The secret has this letter, but somewhere else.
+    Present,
+    /// The secret has no (further) occurrence of this letter.
+    Absent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Letter {
+    pub letter: char,
+    pub mark: Mark,
+}
+
+/// Reason why a word was not accepted as a guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidWord {
+    WrongLength { expected: usize },
+    NotInDictionary,
+    GameOver,
+}
+
+/// Compares `guess` with `secret` letter by letter.
+///
+/// Exact matches are marked first, and every other letter is only marked
+/// present while the secret has unmatched occurrences of it left, so
+/// guessing "eerie" against "crane" only marks the last "e", as correct.
+pub fn score(guess: &str, secret: &str) -> Vec<Letter> {
+    let guess: Vec<char> = guess.chars().collect();
+    let secret: Vec<char> = secret.chars().collect();
+
+    let mut unmatched: HashMap<char, usize> = HashMap::new();
+    for (index, &letter) in secret.iter().enumerate() {
+        if guess.get(index) != Some(&letter) {
+            *unmatched.entry(letter).or_insert(0) += 1;
+        }
+    }
+
+    guess
+        .iter()
+        .enumerate()
+        .map(|(index, &letter)| {
+            let mark = if secret.get(index) == Some(&letter) {
+                Mark::Correct
+            } else {
+                match unmatched.get_mut(&letter) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        Mark::Present
+                    }
+                    _ => Mark::Absent,
+                }
+            };
+            Letter { letter, mark }
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct WordGame<'a> {
+    dictionary: &'a Dictionary,
+    secret: String,
+    history: Vec<String>,
+    won: bool,
+}
+
+impl<'a> WordGame<'a> {
+    /// Starts a game with a known secret.
+    pub fn new(dictionary: &'a Dictionary, secret: &str) -> WordGame<'a> {
+        WordGame {
+            dictionary,
+            secret: secret.trim().to_lowercase(),
+            history: Vec::new(),
+            won: false,
+        }
+    }
+
+    /// Starts a game whose secret is drawn from the dictionary, `None`
+    /// if the dictionary is empty.
+    pub fn random(dictionary: &'a Dictionary, rng: &mut impl Rng) -> Option<WordGame<'a>> {
+        let secret = dictionary.random(rng)?;
+        Some(WordGame::new(dictionary, secret))
+    }
+
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+
+    /// Length of the secret in letters, not bytes.
+    pub fn word_length(&self) -> usize {
+        self.secret.chars().count()
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.history.len() as u32
+    }
+
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.won
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.won || self.attempts() >= MAX_ATTEMPTS
+    }
+
+    /// Takes one line of player input and scores it against the secret.
+    pub fn submit(&mut self, input: &str) -> Result<Vec<Letter>, InvalidWord> {
+        if self.is_over() {
+            return Err(InvalidWord::GameOver);
+        }
+
+        let guess = input.trim().to_lowercase();
+        if guess.chars().count() != self.word_length() {
+            return Err(InvalidWord::WrongLength { expected: self.word_length() });
+        }
+        if !self.dictionary.contains(&guess) {
+            return Err(InvalidWord::NotInDictionary);
+        }
+
+        let letters = score(&guess, &self.secret);
+        self.won = guess == self.secret;
+        self.history.push(guess);
+        Ok(letters)
+    }
+}
+
+impl GuessingGame for WordGame<'_> {
+    type Feedback = Result<Vec<Letter>, InvalidWord>;
+
+    fn submit(&mut self, input: &str) -> Result<Vec<Letter>, InvalidWord> {
+        WordGame::submit(self, input)
+    }
+
+    fn is_over(&self) -> bool {
+        WordGame::is_over(self)
+    }
+
+    fn is_won(&self) -> bool {
+        WordGame::is_won(self)
+    }
+
+    fn attempts(&self) -> u32 {
+        WordGame::attempts(self)
+    }
+}
@@ -0,0 +1,21 @@
+// Hash Maps
+// /////////
+//
+// The word count of 8_3_hash_maps.rs, kept here so that the example and
+// the benchmarks run the same code.
+
+use std::collections::HashMap;
+
+/// How many times each whitespace separated word occurs in `text`.
+///
+/// Each count is updated based on its existing value: `entry` finds the
+/// word's count, `or_insert` starts it at 0 if absent, and the mutable
+/// reference it returns is incremented in place.
+pub fn word_count(text: &str) -> HashMap<&str, i32> {
+    let mut map = HashMap::new();
+    for word in text.split_whitespace() {
+        let count = map.entry(word).or_insert(0);
+        *count += 1;
+    }
+    map
+}
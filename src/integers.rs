@@ -0,0 +1,106 @@
+// Integers
+// ////////
+//
+// Tools around the integer types of 3_2_data_types.rs: reading integer
+// literals in each of their notations, and how each of the standard
+// families of methods deals with overflow.
+
+pub mod literal;
+pub mod overflow;
+
+use std::fmt;
+use std::str::FromStr;
+
+/// The integer types, for choosing one at run time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntegerType {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+}
+
+impl IntegerType {
+    pub const ALL: [IntegerType; 10] = [
+        IntegerType::I8,
+        IntegerType::I16,
+        IntegerType::I32,
+        IntegerType::I64,
+        IntegerType::I128,
+        IntegerType::U8,
+        IntegerType::U16,
+        IntegerType::U32,
+        IntegerType::U64,
+        IntegerType::U128,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            IntegerType::I8 => "i8",
+            IntegerType::I16 => "i16",
+            IntegerType::I32 => "i32",
+            IntegerType::I64 => "i64",
+            IntegerType::I128 => "i128",
+            IntegerType::U8 => "u8",
+            IntegerType::U16 => "u16",
+            IntegerType::U32 => "u32",
+            IntegerType::U64 => "u64",
+            IntegerType::U128 => "u128",
+        }
+    }
+
+    pub fn is_signed(self) -> bool {
+        matches!(
+            self,
+            IntegerType::I8
+                | IntegerType::I16
+                | IntegerType::I32
+                | IntegerType::I64
+                | IntegerType::I128
+        )
+    }
+
+    pub fn bits(self) -> u32 {
+        match self {
+            IntegerType::I8 | IntegerType::U8 => 8,
+            IntegerType::I16 | IntegerType::U16 => 16,
+            IntegerType::I32 | IntegerType::U32 => 32,
+            IntegerType::I64 | IntegerType::U64 => 64,
+            IntegerType::I128 | IntegerType::U128 => 128,
+        }
+    }
+}
+
+impl fmt::Display for IntegerType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownIntegerType(pub String);
+
+impl fmt::Display for UnknownIntegerType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} is not an integer type, such as i32 or u8", self.0)
+    }
+}
+
+impl std::error::Error for UnknownIntegerType {}
+
+impl FromStr for IntegerType {
+    type Err = UnknownIntegerType;
+
+    fn from_str(s: &str) -> Result<IntegerType, UnknownIntegerType> {
+        IntegerType::ALL
+            .into_iter()
+            .find(|integer_type| integer_type.name() == s)
+            .ok_or_else(|| UnknownIntegerType(s.to_string()))
+    }
+}
@@ -0,0 +1,389 @@
+// Integer Literals
+// ////////////////
+//
+// Parses integers written the way Rust source writes them, for reading
+// config values such as masks in hex or flags in binary:
+//
+//     98_222      decimal, with `_` between digits anywhere after the first
+//     0xff        hexadecimal, lowercase `0x` and either case of digits
+//     0o77        octal
+//     0b1111_0000 binary
+//     b'A'        a byte, ASCII or an escape such as b'\n' or b'\x7f'
+//     255u8       any of these with a type suffix, optionally after `_`
+//     -3i64       and with a leading minus sign
+//
+// The type is the suffix's, the one asked for, or `i32` as in Rust.
+// Errors name the column of the text, counting characters from 1, where
+// the problem lies.
+
+use std::error::Error;
+use std::fmt;
+
+use super::IntegerType;
+
+/// An integer of any of the types, as given by a literal's suffix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegerValue {
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+}
+
+impl IntegerValue {
+    pub fn integer_type(self) -> IntegerType {
+        match self {
+            IntegerValue::I8(_) => IntegerType::I8,
+            IntegerValue::I16(_) => IntegerType::I16,
+            IntegerValue::I32(_) => IntegerType::I32,
+            IntegerValue::I64(_) => IntegerType::I64,
+            IntegerValue::I128(_) => IntegerType::I128,
+            IntegerValue::U8(_) => IntegerType::U8,
+            IntegerValue::U16(_) => IntegerType::U16,
+            IntegerValue::U32(_) => IntegerType::U32,
+            IntegerValue::U64(_) => IntegerType::U64,
+            IntegerValue::U128(_) => IntegerType::U128,
+        }
+    }
+}
+
+impl fmt::Display for IntegerValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IntegerValue::I8(value) => value.fmt(f),
+            IntegerValue::I16(value) => value.fmt(f),
+            IntegerValue::I32(value) => value.fmt(f),
+            IntegerValue::I64(value) => value.fmt(f),
+            IntegerValue::I128(value) => value.fmt(f),
+            IntegerValue::U8(value) => value.fmt(f),
+            IntegerValue::U16(value) => value.fmt(f),
+            IntegerValue::U32(value) => value.fmt(f),
+            IntegerValue::U64(value) => value.fmt(f),
+            IntegerValue::U128(value) => value.fmt(f),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiteralError {
+    /// Character of the text, from 1.
+    pub column: usize,
+    pub reason: LiteralErrorReason,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LiteralErrorReason {
+    Empty,
+    /// A sign or prefix not followed by any digit.
+    MissingDigits,
+    InvalidDigit {
+        digit: char,
+        radix: u32,
+    },
+    Unexpected(char),
+    UnknownSuffix(String),
+    /// A suffix other than the type asked for.
+    MismatchedSuffix {
+        expected: IntegerType,
+        found: IntegerType,
+    },
+    OutOfRange {
+        literal: String,
+        integer_type: IntegerType,
+    },
+    UnclosedByte,
+    EmptyByte,
+    NonAsciiByte(char),
+    InvalidEscape(String),
+    NegativeByte,
+}
+
+impl fmt::Display for LiteralError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "column {}: ", self.column)?;
+        match &self.reason {
+            LiteralErrorReason::Empty => write!(f, "expected an integer"),
+            LiteralErrorReason::MissingDigits => write!(f, "expected digits"),
+            LiteralErrorReason::InvalidDigit { digit, radix } => {
+                let base = match radix {
+                    2 => "binary",
+                    8 => "octal",
+                    16 => "hexadecimal",
+                    _ => "decimal",
+                };
+                write!(f, "{digit:?} is not a {base} digit")
+            }
+            LiteralErrorReason::Unexpected(c) => write!(f, "unexpected {c:?}"),
+            LiteralErrorReason::UnknownSuffix(suffix) => {
+                write!(f, "{suffix:?} is not an integer type suffix")
+            }
+            LiteralErrorReason::MismatchedSuffix { expected, found } => {
+                write!(f, "expected {expected}, found the suffix {found}")
+            }
+            LiteralErrorReason::OutOfRange {
+                literal,
+                integer_type,
+            } => {
+                write!(f, "{literal} is out of range for {integer_type}")
+            }
+            LiteralErrorReason::UnclosedByte => write!(f, "the byte literal is never closed"),
+            LiteralErrorReason::EmptyByte => write!(f, "the byte literal is empty"),
+            LiteralErrorReason::NonAsciiByte(c) => {
+                write!(f, "{c:?} is not ASCII, write it as a \\x escape")
+            }
+            LiteralErrorReason::InvalidEscape(escape) => write!(f, "invalid escape {escape}"),
+            LiteralErrorReason::NegativeByte => write!(f, "a byte literal cannot be negative"),
+        }
+    }
+}
+
+impl Error for LiteralError {}
+
+/// The types a literal can be parsed as.
+pub trait FromLiteral: Sized {
+    const TYPE: IntegerType;
+
+    fn from_value(value: IntegerValue) -> Option<Self>;
+}
+
+macro_rules! from_literal {
+    ($($type:ident => $variant:ident),*) => {
+        $(
+            impl FromLiteral for $type {
+                const TYPE: IntegerType = IntegerType::$variant;
+
+                fn from_value(value: IntegerValue) -> Option<$type> {
+                    match value {
+                        IntegerValue::$variant(value) => Some(value),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_literal!(
+    i8 => I8, i16 => I16, i32 => I32, i64 => I64, i128 => I128,
+    u8 => U8, u16 => U16, u32 => U32, u64 => U64, u128 => U128
+);
+
+/// Parses a literal, of the type of its suffix or else `i32`.
+pub fn parse_literal(text: &str) -> Result<IntegerValue, LiteralError> {
+    parse_with(text, None)
+}
+
+/// Parses a literal as `integer_type`, which its suffix, if any, must
+/// name.
+pub fn parse_as(text: &str, integer_type: IntegerType) -> Result<IntegerValue, LiteralError> {
+    parse_with(text, Some(integer_type))
+}
+
+/// Parses a literal as `T`, which its suffix, if any, must name.
+pub fn parse<T: FromLiteral>(text: &str) -> Result<T, LiteralError> {
+    let value = parse_as(text, T::TYPE)?;
+    Ok(T::from_value(value).expect("parse_as returns the type asked for"))
+}
+
+fn parse_with(text: &str, wanted: Option<IntegerType>) -> Result<IntegerValue, LiteralError> {
+    // Surrounding whitespace is ignored, but still counts for columns.
+    let offset = text.chars().take_while(|c| c.is_whitespace()).count();
+    let chars: Vec<char> = text.trim().chars().collect();
+    let error = |index: usize, reason| LiteralError {
+        column: offset + index + 1,
+        reason,
+    };
+
+    if chars.is_empty() {
+        return Err(error(0, LiteralErrorReason::Empty));
+    }
+    let negative = chars[0] == '-';
+    let mut position = usize::from(negative);
+
+    if chars[position..].starts_with(&['b', '\'']) {
+        if negative {
+            return Err(error(0, LiteralErrorReason::NegativeByte));
+        }
+        let byte = parse_byte(&chars, position).map_err(|(index, reason)| error(index, reason))?;
+        return match wanted {
+            Some(expected) if expected != IntegerType::U8 => Err(error(
+                position,
+                LiteralErrorReason::MismatchedSuffix {
+                    expected,
+                    found: IntegerType::U8,
+                },
+            )),
+            _ => Ok(IntegerValue::U8(byte)),
+        };
+    }
+
+    let radix = match chars[position..] {
+        ['0', 'x', ..] => 16,
+        ['0', 'o', ..] => 8,
+        ['0', 'b', ..] => 2,
+        _ => 10,
+    };
+    if radix != 10 {
+        position += 2;
+    }
+
+    let digits_start = position;
+    if radix == 10 && chars.get(position) == Some(&'_') {
+        return Err(error(position, LiteralErrorReason::Unexpected('_')));
+    }
+    let mut magnitude: Option<u128> = Some(0);
+    let mut digits = 0;
+    while let Some(&c) = chars.get(position) {
+        if c == 'i' || c == 'u' || !(c.is_alphanumeric() || c == '_') {
+            break;
+        }
+        if c != '_' {
+            let digit = c.to_digit(radix).ok_or_else(|| {
+                error(
+                    position,
+                    LiteralErrorReason::InvalidDigit { digit: c, radix },
+                )
+            })?;
+            magnitude = magnitude
+                .and_then(|magnitude| magnitude.checked_mul(u128::from(radix)))
+                .and_then(|magnitude| magnitude.checked_add(u128::from(digit)));
+            digits += 1;
+        }
+        position += 1;
+    }
+    if digits == 0 {
+        return Err(error(digits_start, LiteralErrorReason::MissingDigits));
+    }
+
+    let suffix_start = position;
+    while chars.get(position).is_some_and(|c| c.is_alphanumeric()) {
+        position += 1;
+    }
+    let suffix = match &chars[suffix_start..position] {
+        [] => None,
+        suffix => {
+            let suffix: String = suffix.iter().collect();
+            Some(
+                suffix
+                    .parse::<IntegerType>()
+                    .map_err(|_| error(suffix_start, LiteralErrorReason::UnknownSuffix(suffix)))?,
+            )
+        }
+    };
+    if let Some(&c) = chars.get(position) {
+        return Err(error(position, LiteralErrorReason::Unexpected(c)));
+    }
+
+    let integer_type = match (suffix, wanted) {
+        (Some(found), Some(expected)) if found != expected => {
+            return Err(error(
+                suffix_start,
+                LiteralErrorReason::MismatchedSuffix { expected, found },
+            ))
+        }
+        (Some(integer_type), _) | (None, Some(integer_type)) => integer_type,
+        (None, None) => IntegerType::I32,
+    };
+    let out_of_range = || {
+        error(
+            0,
+            LiteralErrorReason::OutOfRange {
+                literal: chars[..suffix_start].iter().collect(),
+                integer_type,
+            },
+        )
+    };
+    let magnitude = magnitude.ok_or_else(out_of_range)?;
+    typed(integer_type, negative, magnitude).ok_or_else(out_of_range)
+}
+
+// The byte literal starting at `chars[start]`, which is the `b`, and
+// making up the rest of `chars`.
+fn parse_byte(chars: &[char], start: usize) -> Result<u8, (usize, LiteralErrorReason)> {
+    let mut position = start + 2;
+    let byte = match chars.get(position) {
+        None => return Err((start, LiteralErrorReason::UnclosedByte)),
+        Some('\'') => return Err((start, LiteralErrorReason::EmptyByte)),
+        Some('\\') => {
+            let escape_start = position;
+            position += 1;
+            let byte = match chars.get(position) {
+                Some('n') => b'\n',
+                Some('r') => b'\r',
+                Some('t') => b'\t',
+                Some('\\') => b'\\',
+                Some('0') => b'\0',
+                Some('\'') => b'\'',
+                Some('"') => b'"',
+                Some('x') => {
+                    let hex: String = chars.iter().skip(position + 1).take(2).collect();
+                    let byte = match (hex.len(), u8::from_str_radix(&hex, 16)) {
+                        (2, Ok(byte)) => byte,
+                        _ => {
+                            let escape = format!("\\x{hex}");
+                            return Err((escape_start, LiteralErrorReason::InvalidEscape(escape)));
+                        }
+                    };
+                    position += 2;
+                    byte
+                }
+                Some(&c) => {
+                    return Err((
+                        escape_start,
+                        LiteralErrorReason::InvalidEscape(format!("\\{c}")),
+                    ))
+                }
+                None => return Err((start, LiteralErrorReason::UnclosedByte)),
+            };
+            position += 1;
+            byte
+        }
+        Some(&c) if c.is_ascii() => {
+            position += 1;
+            c as u8
+        }
+        Some(&c) => return Err((position, LiteralErrorReason::NonAsciiByte(c))),
+    };
+
+    match chars.get(position) {
+        Some('\'') => {}
+        _ => return Err((start, LiteralErrorReason::UnclosedByte)),
+    }
+    match chars.get(position + 1) {
+        None => Ok(byte),
+        Some(&c) => Err((position + 1, LiteralErrorReason::Unexpected(c))),
+    }
+}
+
+// The value of `-magnitude` or `magnitude` as `integer_type`, if in range.
+fn typed(integer_type: IntegerType, negative: bool, magnitude: u128) -> Option<IntegerValue> {
+    let signed = if negative {
+        0i128.checked_sub_unsigned(magnitude)
+    } else {
+        i128::try_from(magnitude).ok()
+    };
+    let unsigned = if negative && magnitude != 0 {
+        None
+    } else {
+        Some(magnitude)
+    };
+
+    Some(match integer_type {
+        IntegerType::I8 => IntegerValue::I8(signed?.try_into().ok()?),
+        IntegerType::I16 => IntegerValue::I16(signed?.try_into().ok()?),
+        IntegerType::I32 => IntegerValue::I32(signed?.try_into().ok()?),
+        IntegerType::I64 => IntegerValue::I64(signed?.try_into().ok()?),
+        IntegerType::I128 => IntegerValue::I128(signed?),
+        IntegerType::U8 => IntegerValue::U8(unsigned?.try_into().ok()?),
+        IntegerType::U16 => IntegerValue::U16(unsigned?.try_into().ok()?),
+        IntegerType::U32 => IntegerValue::U32(unsigned?.try_into().ok()?),
+        IntegerType::U64 => IntegerValue::U64(unsigned?.try_into().ok()?),
+        IntegerType::U128 => IntegerValue::U128(unsigned?),
+    })
+}
@@ -0,0 +1,463 @@
+// Integer Overflow
+// ////////////////
+//
+// The standard library offers four families of methods for arithmetic
+// whose result may not fit its type, here as four `OverflowPolicy`s:
+//
+// * `Wrapping`, `wrapping_*`: wraps around, 250u8 + 10 is 4;
+// * `Checked`, `checked_*`: `None` on overflow;
+// * `Overflowing`, `overflowing_*`: the wrapped result and whether it
+//   overflowed, `(4, true)`;
+// * `Saturating`, `saturating_*`: stops at the type's bounds, 255.
+//
+// A fifth one, `Operator`, is what the plain operators `+`, `-`, `*`, `/`
+// and `pow` do: panic on overflow when overflow checks are on, as they
+// are in debug builds, and wrap otherwise. Dividing `MIN` by -1 panics
+// either way. Rather than running into the panic, which would need the
+// process-wide panic hook silenced, the operator is not applied when the
+// checked result shows it would panic, and the panic message is returned.
+//
+// Division by zero is not an overflow, and is an `ArithmeticError` for
+// every policy, as is a negative exponent.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use super::literal::{self, FromLiteral, LiteralError};
+use super::IntegerType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+    /// Unary, its second operand is ignored.
+    Neg,
+}
+
+impl Operation {
+    pub const ALL: [Operation; 6] = [
+        Operation::Add,
+        Operation::Sub,
+        Operation::Mul,
+        Operation::Div,
+        Operation::Pow,
+        Operation::Neg,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Operation::Add => "add",
+            Operation::Sub => "sub",
+            Operation::Mul => "mul",
+            Operation::Div => "div",
+            Operation::Pow => "pow",
+            Operation::Neg => "neg",
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Operation::Add => "+",
+            Operation::Sub => "-",
+            Operation::Mul => "*",
+            Operation::Div => "/",
+            Operation::Pow => "^",
+            Operation::Neg => "-",
+        }
+    }
+
+    /// The number of operands.
+    pub fn arity(self) -> usize {
+        match self {
+            Operation::Neg => 1,
+            _ => 2,
+        }
+    }
+
+    fn check<T: Integer>(self, rhs: T) -> Result<(), ArithmeticError> {
+        match self {
+            Operation::Div if rhs == T::ZERO => Err(ArithmeticError::DivisionByZero),
+            Operation::Pow if rhs.to_exponent().is_none() => Err(ArithmeticError::InvalidExponent),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownOperation(pub String);
+
+impl fmt::Display for UnknownOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:?} is not one of add, sub, mul, div, pow or neg",
+            self.0
+        )
+    }
+}
+
+impl Error for UnknownOperation {}
+
+impl FromStr for Operation {
+    type Err = UnknownOperation;
+
+    /// By name, or by symbol for the binary operations.
+    fn from_str(s: &str) -> Result<Operation, UnknownOperation> {
+        Operation::ALL
+            .into_iter()
+            .find(|operation| {
+                operation.name() == s || (operation.arity() == 2 && operation.symbol() == s)
+            })
+            .ok_or_else(|| UnknownOperation(s.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithmeticError {
+    DivisionByZero,
+    /// A negative exponent, or one beyond `u32::MAX`.
+    InvalidExponent,
+}
+
+impl fmt::Display for ArithmeticError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArithmeticError::DivisionByZero => write!(f, "division by zero"),
+            ArithmeticError::InvalidExponent => {
+                write!(f, "the exponent must be between 0 and {}", u32::MAX)
+            }
+        }
+    }
+}
+
+impl Error for ArithmeticError {}
+
+/// The integer types the policies apply to, implemented for `i8` to
+/// `i128` and `u8` to `u128`.
+///
+/// Each method applies `operation` with one family; the exponent of `Pow`
+/// must be valid.
+pub trait Integer:
+    Copy + PartialEq + fmt::Debug + fmt::Display + FromStr + FromLiteral + 'static
+{
+    const ZERO: Self;
+    const MIN: Self;
+    const MAX: Self;
+
+    fn to_exponent(self) -> Option<u32>;
+    fn wrapping(self, operation: Operation, rhs: Self) -> Self;
+    fn checked(self, operation: Operation, rhs: Self) -> Option<Self>;
+    fn overflowing(self, operation: Operation, rhs: Self) -> (Self, bool);
+    fn saturating(self, operation: Operation, rhs: Self) -> Self;
+    /// With the plain operators, which may panic.
+    fn operator(self, operation: Operation, rhs: Self) -> Self;
+}
+
+// Negation is subtraction from zero, which also covers the unsigned types
+// that have no `saturating_neg`. Only the operator, `-x`, is given for each
+// type, since the unsigned types have none.
+macro_rules! integer {
+    ($($type:ident => $variant:ident, negate: $negate:expr;)*) => {
+        $(
+            impl Integer for $type {
+                const ZERO: $type = 0;
+                const MIN: $type = $type::MIN;
+                const MAX: $type = $type::MAX;
+
+                fn to_exponent(self) -> Option<u32> {
+                    u32::try_from(self).ok()
+                }
+
+                fn wrapping(self, operation: Operation, rhs: $type) -> $type {
+                    match operation {
+                        Operation::Add => self.wrapping_add(rhs),
+                        Operation::Sub => self.wrapping_sub(rhs),
+                        Operation::Mul => self.wrapping_mul(rhs),
+                        Operation::Div => self.wrapping_div(rhs),
+                        Operation::Pow => self.wrapping_pow(exponent(rhs)),
+                        Operation::Neg => (0 as $type).wrapping_sub(self),
+                    }
+                }
+
+                fn checked(self, operation: Operation, rhs: $type) -> Option<$type> {
+                    match operation {
+                        Operation::Add => self.checked_add(rhs),
+                        Operation::Sub => self.checked_sub(rhs),
+                        Operation::Mul => self.checked_mul(rhs),
+                        Operation::Div => self.checked_div(rhs),
+                        Operation::Pow => self.checked_pow(exponent(rhs)),
+                        Operation::Neg => (0 as $type).checked_sub(self),
+                    }
+                }
+
+                fn overflowing(self, operation: Operation, rhs: $type) -> ($type, bool) {
+                    match operation {
+                        Operation::Add => self.overflowing_add(rhs),
+                        Operation::Sub => self.overflowing_sub(rhs),
+                        Operation::Mul => self.overflowing_mul(rhs),
+                        Operation::Div => self.overflowing_div(rhs),
+                        Operation::Pow => self.overflowing_pow(exponent(rhs)),
+                        Operation::Neg => (0 as $type).overflowing_sub(self),
+                    }
+                }
+
+                fn saturating(self, operation: Operation, rhs: $type) -> $type {
+                    match operation {
+                        Operation::Add => self.saturating_add(rhs),
+                        Operation::Sub => self.saturating_sub(rhs),
+                        Operation::Mul => self.saturating_mul(rhs),
+                        Operation::Div => self.saturating_div(rhs),
+                        Operation::Pow => self.saturating_pow(exponent(rhs)),
+                        Operation::Neg => (0 as $type).saturating_sub(self),
+                    }
+                }
+
+                fn operator(self, operation: Operation, rhs: $type) -> $type {
+                    match operation {
+                        Operation::Add => self + rhs,
+                        Operation::Sub => self - rhs,
+                        Operation::Mul => self * rhs,
+                        Operation::Div => self / rhs,
+                        Operation::Pow => self.pow(exponent(rhs)),
+                        Operation::Neg => ($negate)(self),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+integer! {
+    i8 => I8, negate: |x: i8| -x;
+    i16 => I16, negate: |x: i16| -x;
+    i32 => I32, negate: |x: i32| -x;
+    i64 => I64, negate: |x: i64| -x;
+    i128 => I128, negate: |x: i128| -x;
+    u8 => U8, negate: |x: u8| 0 - x;
+    u16 => U16, negate: |x: u16| 0 - x;
+    u32 => U32, negate: |x: u32| 0 - x;
+    u64 => U64, negate: |x: u64| 0 - x;
+    u128 => U128, negate: |x: u128| 0 - x;
+}
+
+fn exponent<T: Integer>(rhs: T) -> u32 {
+    rhs.to_exponent().expect("the exponent was checked")
+}
+
+/// What to do with a result that does not fit its type.
+pub trait OverflowPolicy {
+    const NAME: &'static str;
+    type Output<T: Integer>: fmt::Debug;
+
+    /// `a operation b`, or `operation a` for `Neg`.
+    fn apply<T: Integer>(
+        operation: Operation,
+        a: T,
+        b: T,
+    ) -> Result<Self::Output<T>, ArithmeticError>;
+}
+
+pub struct Wrapping;
+pub struct Checked;
+pub struct Overflowing;
+pub struct Saturating;
+pub struct Operator;
+
+impl OverflowPolicy for Wrapping {
+    const NAME: &'static str = "wrapping";
+    type Output<T: Integer> = T;
+
+    fn apply<T: Integer>(operation: Operation, a: T, b: T) -> Result<T, ArithmeticError> {
+        operation.check(b)?;
+        Ok(a.wrapping(operation, b))
+    }
+}
+
+impl OverflowPolicy for Checked {
+    const NAME: &'static str = "checked";
+    type Output<T: Integer> = Option<T>;
+
+    fn apply<T: Integer>(operation: Operation, a: T, b: T) -> Result<Option<T>, ArithmeticError> {
+        operation.check(b)?;
+        Ok(a.checked(operation, b))
+    }
+}
+
+impl OverflowPolicy for Overflowing {
+    const NAME: &'static str = "overflowing";
+    type Output<T: Integer> = (T, bool);
+
+    fn apply<T: Integer>(operation: Operation, a: T, b: T) -> Result<(T, bool), ArithmeticError> {
+        operation.check(b)?;
+        Ok(a.overflowing(operation, b))
+    }
+}
+
+impl OverflowPolicy for Saturating {
+    const NAME: &'static str = "saturating";
+    type Output<T: Integer> = T;
+
+    fn apply<T: Integer>(operation: Operation, a: T, b: T) -> Result<T, ArithmeticError> {
+        operation.check(b)?;
+        Ok(a.saturating(operation, b))
+    }
+}
+
+/// The plain operators; `Err` holds the message they would panic with.
+///
+/// Nothing panics: the operator only runs when the checked result shows
+/// it would not, so no panic hook needs silencing.
+impl OverflowPolicy for Operator {
+    const NAME: &'static str = "operator";
+    type Output<T: Integer> = Result<T, String>;
+
+    fn apply<T: Integer>(
+        operation: Operation,
+        a: T,
+        b: T,
+    ) -> Result<Result<T, String>, ArithmeticError> {
+        operation.check(b)?;
+        // Overflow checks are taken to be on exactly in debug builds, as
+        // is the default for both profiles.
+        let panics = a.checked(operation, b).is_none()
+            && (cfg!(debug_assertions) || operation == Operation::Div);
+        if panics {
+            let verb = match operation {
+                Operation::Add => "add",
+                Operation::Sub => "subtract",
+                // Unsigned negation is a subtraction from zero.
+                Operation::Neg if !T::TYPE.is_signed() => "subtract",
+                Operation::Neg => "negate",
+                Operation::Mul | Operation::Pow => "multiply",
+                Operation::Div => "divide",
+            };
+            return Ok(Err(format!("attempt to {verb} with overflow")));
+        }
+        Ok(Ok(a.operator(operation, b)))
+    }
+}
+
+/// The result of an operation under every policy, by policy name, as
+/// printed by the `overflow` binary.
+pub fn compare<T: Integer>(
+    operation: Operation,
+    a: T,
+    b: T,
+) -> Result<[(&'static str, String); 5], ArithmeticError> {
+    let operator = match Operator::apply(operation, a, b)? {
+        Ok(value) => value.to_string(),
+        Err(message) => format!("panics: {message}"),
+    };
+    Ok([
+        (
+            Wrapping::NAME,
+            Wrapping::apply(operation, a, b)?.to_string(),
+        ),
+        (
+            Checked::NAME,
+            format!("{:?}", Checked::apply(operation, a, b)?),
+        ),
+        (
+            Overflowing::NAME,
+            format!("{:?}", Overflowing::apply(operation, a, b)?),
+        ),
+        (
+            Saturating::NAME,
+            Saturating::apply(operation, a, b)?.to_string(),
+        ),
+        (Operator::NAME, operator),
+    ])
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompareError {
+    WrongOperandCount {
+        expected: usize,
+        found: usize,
+    },
+    /// An operand that is not a literal of the chosen type.
+    InvalidOperand {
+        operand: String,
+        error: LiteralError,
+    },
+    Arithmetic(ArithmeticError),
+}
+
+impl fmt::Display for CompareError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompareError::WrongOperandCount { expected: 1, found } => {
+                write!(f, "expected 1 operand, got {found}")
+            }
+            CompareError::WrongOperandCount { expected, found } => {
+                write!(f, "expected {expected} operands, got {found}")
+            }
+            CompareError::InvalidOperand { operand, error } => write!(f, "{operand:?}, {error}"),
+            CompareError::Arithmetic(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl Error for CompareError {}
+
+impl From<ArithmeticError> for CompareError {
+    fn from(error: ArithmeticError) -> CompareError {
+        CompareError::Arithmetic(error)
+    }
+}
+
+/// `compare` for a type chosen at run time, parsing the operands as
+/// literals of that type.
+pub fn compare_as(
+    integer_type: IntegerType,
+    operation: Operation,
+    operands: &[&str],
+) -> Result<[(&'static str, String); 5], CompareError> {
+    if operands.len() != operation.arity() {
+        return Err(CompareError::WrongOperandCount {
+            expected: operation.arity(),
+            found: operands.len(),
+        });
+    }
+
+    fn parse_and_compare<T: Integer>(
+        operation: Operation,
+        operands: &[&str],
+    ) -> Result<[(&'static str, String); 5], CompareError> {
+        let parse = |operand: &str| {
+            literal::parse::<T>(operand).map_err(|error| CompareError::InvalidOperand {
+                operand: operand.to_string(),
+                error,
+            })
+        };
+        let a = parse(operands[0])?;
+        let b = match operands.get(1) {
+            Some(operand) => parse(operand)?,
+            None => T::ZERO,
+        };
+        Ok(compare(operation, a, b)?)
+    }
+
+    match integer_type {
+        IntegerType::I8 => parse_and_compare::<i8>(operation, operands),
+        IntegerType::I16 => parse_and_compare::<i16>(operation, operands),
+        IntegerType::I32 => parse_and_compare::<i32>(operation, operands),
+        IntegerType::I64 => parse_and_compare::<i64>(operation, operands),
+        IntegerType::I128 => parse_and_compare::<i128>(operation, operands),
+        IntegerType::U8 => parse_and_compare::<u8>(operation, operands),
+        IntegerType::U16 => parse_and_compare::<u16>(operation, operands),
+        IntegerType::U32 => parse_and_compare::<u32>(operation, operands),
+        IntegerType::U64 => parse_and_compare::<u64>(operation, operands),
+        IntegerType::U128 => parse_and_compare::<u128>(operation, operands),
+    }
+}
@@ -0,0 +1,17 @@
+// Library crate of the package.
+//
+// Code that outgrew a single example binary under `src/bin` lives here,
+// so that several binaries (and the integration tests) can share it
+// through `use rust_book::...`.
+
+pub mod compound;
+pub mod english;
+pub mod fibonacci;
+pub mod guessing_game;
+pub mod hash_maps;
+pub mod integers;
+pub mod memo;
+pub mod slice_type;
+pub mod songs;
+pub mod template;
+pub mod units;
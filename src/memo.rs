@@ -0,0 +1,185 @@
+// Memoization
+// ///////////
+//
+// `Memoized` wraps a pure function and remembers its results in a
+// `LruCache`, a `HashMap` bounded to a fixed number of entries that evicts
+// the least recently used one when full.
+//
+// The function receives a `recurse` callback along with its argument, and
+// calls it instead of calling itself, so that the recursive calls go
+// through the cache too:
+//
+//     let mut fibonacci = Memoized::new(100, |recurse: Recurse<u64, u64>, n| {
+//         if n < 2 { n } else { recurse(n - 1) + recurse(n - 2) }
+//     });
+//     assert_eq!(fibonacci.call(90), 2_880_067_194_370_816_120);
+//
+// A function that does not recurse simply ignores `recurse`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// A map holding at most `capacity` entries, evicting the least recently
+/// used one to make room.
+#[derive(Debug, Clone)]
+pub struct LruCache<K, V> {
+    capacity: usize,
+    // Each value with the tick at which it was last used.
+    entries: HashMap<K, (V, u64)>,
+    // The keys by the tick at which they were last used, oldest first.
+    recency: BTreeMap<u64, K>,
+    tick: u64,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    /// A capacity of 0 makes a cache that never holds anything.
+    pub fn new(capacity: usize) -> Self {
+        LruCache {
+            capacity,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Does not count as a use.
+    pub fn contains(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// The value for `key`, which becomes the most recently used.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let tick = self.next_tick();
+        let (value, last_used) = self.entries.get_mut(key)?;
+        let key = self
+            .recency
+            .remove(last_used)
+            .expect("every entry has a recency");
+        self.recency.insert(tick, key);
+        *last_used = tick;
+        Some(value)
+    }
+
+    /// Inserts or replaces the value for `key`, evicting the least recently
+    /// used entry if the cache is full. Returns the evicted entry.
+    pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if self.capacity == 0 {
+            return Some((key, value));
+        }
+
+        let tick = self.next_tick();
+        if let Some((_, last_used)) = self.entries.insert(key.clone(), (value, tick)) {
+            self.recency.remove(&last_used);
+            self.recency.insert(tick, key);
+            return None;
+        }
+        self.recency.insert(tick, key);
+
+        if self.entries.len() <= self.capacity {
+            return None;
+        }
+        let (_, oldest) = self.recency.pop_first()?;
+        let (value, _) = self.entries.remove(&oldest)?;
+        Some((oldest, value))
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+}
+
+/// The callback through which a memoized function calls itself.
+pub type Recurse<'a, K, V> = &'a mut dyn FnMut(K) -> V;
+
+/// A function whose results are cached, recursive calls included.
+pub struct Memoized<K, V, F> {
+    function: F,
+    cache: LruCache<K, V>,
+    hits: u64,
+    misses: u64,
+}
+
+impl<K, V, F> Memoized<K, V, F>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    F: Fn(Recurse<K, V>, K) -> V,
+{
+    /// Caches the results of `function` for up to `capacity` arguments.
+    pub fn new(capacity: usize, function: F) -> Self {
+        Memoized {
+            function,
+            cache: LruCache::new(capacity),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub fn call(&mut self, argument: K) -> V {
+        let Memoized {
+            function,
+            cache,
+            hits,
+            misses,
+        } = self;
+        lookup(function, cache, hits, misses, argument)
+    }
+
+    pub fn cache(&self) -> &LruCache<K, V> {
+        &self.cache
+    }
+
+    /// Calls, recursive or not, answered from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Calls, recursive or not, that ran the function.
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+}
+
+fn lookup<K, V, F>(
+    function: &F,
+    cache: &mut LruCache<K, V>,
+    hits: &mut u64,
+    misses: &mut u64,
+    argument: K,
+) -> V
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    F: Fn(Recurse<K, V>, K) -> V,
+{
+    if let Some(value) = cache.get(&argument) {
+        *hits += 1;
+        return value.clone();
+    }
+
+    *misses += 1;
+    let value = function(
+        &mut |argument| lookup(function, cache, hits, misses, argument),
+        argument.clone(),
+    );
+    cache.insert(argument, value.clone());
+    value
+}
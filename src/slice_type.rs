@@ -0,0 +1,59 @@
+// Slice Type
+// //////////
+//
+// The three versions of `first_word` from 4_3_slice_type.rs, kept here so
+// that the example and the benchmarks run the same code.
+//
+// They are kept as the chapter writes them, `&String` parameters and all,
+// since the point is how the signatures compare.
+
+/// The index of the end of the first word of `s`.
+///
+/// Finding the first word by the first space is problematic, because the
+/// index returned is only meaningful in relation to the string it was
+/// computed from. It is a separate value from the string, so there is no
+/// guarantee that it will still be valid later in the program.
+#[allow(clippy::ptr_arg)]
+pub fn first_word(s: &String) -> usize {
+    // Iterate over the bytes of the string, with their indices, to find
+    // the first space.
+    let bytes = s.as_bytes();
+
+    for (i, &item) in bytes.iter().enumerate() {
+        if item == b' ' {
+            return i;
+        }
+    }
+
+    // No space: the whole string is one word.
+    s.len()
+}
+
+/// The first word of `s`, as a string slice tied to `s`.
+#[allow(clippy::ptr_arg, clippy::redundant_slicing)]
+pub fn first_word_2(s: &String) -> &str {
+    let bytes = s.as_bytes();
+
+    for (i, &item) in bytes.iter().enumerate() {
+        if item == b' ' {
+            return &s[0..i];
+        }
+    }
+
+    &s[..]
+}
+
+/// `first_word_2` taking a `&str` rather than a `&String`, so that it
+/// accepts both string slices and references to `String`s.
+#[allow(clippy::redundant_slicing)]
+pub fn first_word_3(s: &str) -> &str {
+    let bytes = s.as_bytes();
+
+    for (i, &item) in bytes.iter().enumerate() {
+        if item == b' ' {
+            return &s[0..i];
+        }
+    }
+
+    &s[..]
+}
@@ -0,0 +1,286 @@
+// Cumulative Songs
+// ////////////////
+//
+// Songs like "The Twelve Days of Christmas", where each verse introduces
+// an item and then repeats the lines of every item so far, newest first.
+// A song is data: the lines that open and close every verse, and for each
+// item the line it contributes to the cumulative part.
+//
+// Songs are read from TOML or JSON files such as data/songs/*.toml. The
+// lines of a song may contain placeholders, replaced in each verse:
+//
+// * `{number}`, the number of the verse, from 1;
+// * `{ordinal}`, the same as an ordinal: 1st, 2nd, 3rd, 4th, ...;
+// * `{ordinal_words}`, the ordinal in words: first, second, third, ...;
+// * `{name}`, the name of the verse's item.
+//
+// Each item may also have
+//
+// * a `comment`, sung right after the opening of its own verse only;
+// * an `after_others` line, used instead of its `line` once other items
+//   come before it, such as "And a partridge in a pear tree";
+// * `ends_song`, for a last item whose verse stops after its comment,
+//   such as the old lady's horse.
+//
+// `render` lays a song out as text, Markdown or HTML through the templates
+// in data/templates.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::english::{Ordinal, OrdinalWords};
+use crate::template::{Format, Template, TemplateError};
+
+/// The songs in data/songs, by name.
+pub const BUILT_IN: [(&str, &str); 3] = [
+    (
+        "twelve-days",
+        include_str!("../data/songs/twelve_days.toml"),
+    ),
+    (
+        "old-macdonald",
+        include_str!("../data/songs/old_macdonald.toml"),
+    ),
+    ("old-lady", include_str!("../data/songs/old_lady.toml")),
+];
+
+#[derive(Debug)]
+pub enum SongError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+    /// Neither a .toml nor a .json file.
+    UnknownFormat(PathBuf),
+    Invalid(String),
+}
+
+impl fmt::Display for SongError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SongError::Io(error) => write!(f, "{error}"),
+            SongError::Toml(error) => write!(f, "invalid TOML: {error}"),
+            SongError::Json(error) => write!(f, "invalid JSON: {error}"),
+            SongError::UnknownFormat(path) => {
+                write!(f, "{} is neither a .toml nor a .json file", path.display())
+            }
+            SongError::Invalid(reason) => write!(f, "invalid song: {reason}"),
+        }
+    }
+}
+
+impl Error for SongError {}
+
+impl From<io::Error> for SongError {
+    fn from(error: io::Error) -> SongError {
+        SongError::Io(error)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Song {
+    pub title: String,
+    /// The lines starting every verse.
+    #[serde(default)]
+    pub opening: Vec<String>,
+    /// The lines ending every verse.
+    #[serde(default)]
+    pub closing: Vec<String>,
+    /// In the order they are introduced, one per verse.
+    pub items: Vec<Item>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Item {
+    pub name: String,
+    /// Only left out by an item that ends the song.
+    #[serde(default)]
+    pub line: Option<String>,
+    #[serde(default)]
+    pub after_others: Option<String>,
+    #[serde(default)]
+    pub comment: Option<String>,
+    #[serde(default)]
+    pub ends_song: bool,
+}
+
+impl Song {
+    pub fn from_toml(text: &str) -> Result<Song, SongError> {
+        toml::from_str::<Song>(text)
+            .map_err(SongError::Toml)?
+            .validated()
+    }
+
+    pub fn from_json(text: &str) -> Result<Song, SongError> {
+        serde_json::from_str::<Song>(text)
+            .map_err(SongError::Json)?
+            .validated()
+    }
+
+    /// Reads a song from a file, in TOML or JSON depending on its
+    /// extension.
+    pub fn load(path: impl AsRef<Path>) -> Result<Song, SongError> {
+        let path = path.as_ref();
+        let extension = path.extension().and_then(|extension| extension.to_str());
+        match extension.map(str::to_ascii_lowercase).as_deref() {
+            Some("toml") => Song::from_toml(&fs::read_to_string(path)?),
+            Some("json") => Song::from_json(&fs::read_to_string(path)?),
+            _ => Err(SongError::UnknownFormat(path.to_path_buf())),
+        }
+    }
+
+    /// One of the `BUILT_IN` songs.
+    pub fn built_in(name: &str) -> Option<Song> {
+        let (_, text) = BUILT_IN.iter().find(|(built_in, _)| *built_in == name)?;
+        Some(Song::from_toml(text).expect("built-in songs are valid"))
+    }
+
+    fn validated(self) -> Result<Song, SongError> {
+        if self.items.is_empty() {
+            return Err(SongError::Invalid(
+                "a song needs at least one item".to_string(),
+            ));
+        }
+        let last = self.items.len() - 1;
+        for (index, item) in self.items.iter().enumerate() {
+            if item.ends_song && index != last {
+                return Err(SongError::Invalid(format!(
+                    "only the last item can end the song, not \"{}\"",
+                    item.name
+                )));
+            }
+            if item.line.is_none() && !(item.ends_song && index == last) {
+                return Err(SongError::Invalid(format!("\"{}\" has no line", item.name)));
+            }
+        }
+        Ok(self)
+    }
+
+    pub fn verse_count(&self) -> usize {
+        self.items.len()
+    }
+
+    /// The lines of verse `number`, counting from 1.
+    pub fn verse(&self, number: usize) -> Option<Vec<String>> {
+        Some(self.verse_parts(number)?.lines)
+    }
+
+    /// Every verse, in order.
+    pub fn verses(&self) -> impl Iterator<Item = Vec<String>> + '_ {
+        (1..=self.items.len()).filter_map(|number| self.verse(number))
+    }
+
+    /// The song through one of the templates in data/templates.
+    pub fn render(&self, format: Format) -> String {
+        let source = match format {
+            Format::Text => include_str!("../data/templates/song.txt"),
+            Format::Markdown => include_str!("../data/templates/song.md"),
+            Format::Html => include_str!("../data/templates/song.html"),
+        };
+        let template = Template::parse(source).expect("built-in templates are valid");
+        self.render_with(&template, format)
+            .expect("built-in templates render any song")
+    }
+
+    /// The song through `template`, which sees the song's `title` and its
+    /// `verses`, each with a `number`, the `name` of its item and its
+    /// `opening`, `comment`, `cumulative` and `closing` parts, as well as
+    /// all its `lines`.
+    pub fn render_with(
+        &self,
+        template: &Template,
+        format: Format,
+    ) -> Result<String, TemplateError> {
+        #[derive(Serialize)]
+        struct Context<'a> {
+            title: &'a str,
+            verses: Vec<VerseParts>,
+        }
+
+        let verses = (1..=self.items.len())
+            .filter_map(|number| self.verse_parts(number))
+            .collect();
+        template.render(
+            &Context {
+                title: &self.title,
+                verses,
+            },
+            format,
+        )
+    }
+
+    fn verse_parts(&self, number: usize) -> Option<VerseParts> {
+        let item = self.items.get(number.checked_sub(1)?)?;
+        let fill = |line: &str| {
+            line.replace("{number}", &number.to_string())
+                .replace("{ordinal}", &Ordinal(number as u64).to_string())
+                .replace("{ordinal_words}", &OrdinalWords(number as u64).to_string())
+                .replace("{name}", &item.name)
+        };
+
+        let mut cumulative = Vec::new();
+        if !item.ends_song {
+            for (index, earlier) in self.items[..number].iter().enumerate().rev() {
+                let line = match &earlier.after_others {
+                    Some(after_others) if index < number - 1 => after_others,
+                    _ => earlier.line.as_ref().expect("validated songs have lines"),
+                };
+                cumulative.push(fill(line));
+            }
+        }
+        let closing = if item.ends_song {
+            Vec::new()
+        } else {
+            self.closing.iter().map(|line| fill(line)).collect()
+        };
+
+        let opening: Vec<String> = self.opening.iter().map(|line| fill(line)).collect();
+        let comment = item.comment.as_deref().map(fill);
+        let mut lines = opening.clone();
+        lines.extend(comment.clone());
+        lines.extend(cumulative.iter().cloned());
+        lines.extend(closing.iter().cloned());
+
+        Some(VerseParts {
+            number,
+            name: item.name.clone(),
+            opening,
+            comment,
+            cumulative,
+            closing,
+            lines,
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct VerseParts {
+    number: usize,
+    name: String,
+    opening: Vec<String>,
+    comment: Option<String>,
+    cumulative: Vec<String>,
+    closing: Vec<String>,
+    lines: Vec<String>,
+}
+
+/// The whole song, verses separated by a blank line.
+impl fmt::Display for Song {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, verse) in self.verses().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            for line in verse {
+                writeln!(f, "{line}")?;
+            }
+        }
+        Ok(())
+    }
+}
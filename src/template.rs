@@ -0,0 +1,357 @@
+// Templates
+// /////////
+//
+// A small template language for rendering the same data as plain text,
+// Markdown or HTML, in the style of Jinja:
+//
+//     # {{ title }}
+//     {% for verse in verses %}
+//     ## The {{ verse.number | ordinal_words }} verse
+//     {% if verse.comment %}
+//     *{{ verse.comment }}*
+//     {% endif %}
+//     {% endfor %}
+//
+// * `{{ path | filter | ... }}` prints a value. A path is a dotted chain of
+//   names looked up in the loop variables, then in the data.
+// * `{% for name in path %} ... {% endfor %}` repeats for every item of a
+//   list. Inside, `loop.index` counts from 1, `loop.index0` from 0, and
+//   `loop.first`, `loop.last` and `loop.length` are also set.
+// * `{% if [not] path %} ... {% else %} ... {% endif %}` tests whether a
+//   value is set: null, false, 0, "" and empty lists and maps are not.
+// * `{# ... #}` is a comment.
+//
+// A `{% %}` tag or comment alone on its line takes the whole line with it,
+// newline included, so that tags can sit on their own lines in a template
+// for text without leaving blank lines behind.
+//
+// The data is anything `Serialize`, through `serde_json::Value`.
+// Printed values are escaped for the output format: `&<>"'` in HTML, and
+// the characters that would start emphasis, code or links in Markdown.
+// The `raw` filter skips the escaping.
+
+use std::error::Error;
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::english::{Cardinal, Ordinal, OrdinalWords};
+
+mod parser;
+
+use parser::{Expression, Node};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Markdown,
+    Html,
+}
+
+impl Format {
+    fn escape(self, text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            match (self, c) {
+                (Format::Html, '&') => escaped.push_str("&amp;"),
+                (Format::Html, '<') => escaped.push_str("&lt;"),
+                (Format::Html, '>') => escaped.push_str("&gt;"),
+                (Format::Html, '"') => escaped.push_str("&quot;"),
+                (Format::Html, '\'') => escaped.push_str("&#39;"),
+                (Format::Markdown, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#') => {
+                    escaped.push('\\');
+                    escaped.push(c);
+                }
+                _ => escaped.push(c),
+            }
+        }
+        escaped
+    }
+}
+
+/// A template that failed to parse or render.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateError {
+    /// Line of the template, from 1.
+    pub line: usize,
+    pub reason: TemplateErrorReason,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateErrorReason {
+    /// A `{{`, `{%` or `{#` without its closing delimiter.
+    Unclosed(&'static str),
+    /// A `for` or `if` block without its end tag.
+    UnclosedBlock(&'static str),
+    UnexpectedTag(String),
+    InvalidTag(String),
+    InvalidPath(String),
+    UnknownFilter(String),
+    Undefined(String),
+    NotAList(String),
+    /// A list or map where text was expected.
+    NotPrintable(String),
+    FilterInput {
+        filter: &'static str,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.reason {
+            TemplateErrorReason::Unclosed(delimiter) => write!(f, "missing {delimiter}"),
+            TemplateErrorReason::UnclosedBlock(tag) => {
+                write!(f, "{{% {tag} %}} is never closed by {{% end{tag} %}}")
+            }
+            TemplateErrorReason::UnexpectedTag(tag) => write!(f, "unexpected {{% {tag} %}}"),
+            TemplateErrorReason::InvalidTag(tag) => write!(f, "invalid tag {{% {tag} %}}"),
+            TemplateErrorReason::InvalidPath(path) => write!(f, "{path:?} is not a valid path"),
+            TemplateErrorReason::UnknownFilter(name) => write!(f, "no filter named {name:?}"),
+            TemplateErrorReason::Undefined(path) => write!(f, "{path} is not defined"),
+            TemplateErrorReason::NotAList(path) => write!(f, "{path} is not a list"),
+            TemplateErrorReason::NotPrintable(path) => {
+                write!(f, "{path} is a list or a map and cannot be printed")
+            }
+            TemplateErrorReason::FilterInput { filter, expected } => {
+                write!(f, "the {filter} filter expects {expected}")
+            }
+        }
+    }
+}
+
+impl Error for TemplateError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Filter {
+    Ordinal,
+    OrdinalWords,
+    Cardinal,
+    Capitalize,
+    Upper,
+    Lower,
+    Length,
+    Raw,
+}
+
+impl Filter {
+    fn parse(name: &str) -> Option<Filter> {
+        Some(match name {
+            "ordinal" => Filter::Ordinal,
+            "ordinal_words" => Filter::OrdinalWords,
+            "cardinal" => Filter::Cardinal,
+            "capitalize" => Filter::Capitalize,
+            "upper" => Filter::Upper,
+            "lower" => Filter::Lower,
+            "length" => Filter::Length,
+            "raw" => Filter::Raw,
+            _ => return None,
+        })
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Filter::Ordinal => "ordinal",
+            Filter::OrdinalWords => "ordinal_words",
+            Filter::Cardinal => "cardinal",
+            Filter::Capitalize => "capitalize",
+            Filter::Upper => "upper",
+            Filter::Lower => "lower",
+            Filter::Length => "length",
+            Filter::Raw => "raw",
+        }
+    }
+
+    fn apply(self, value: Value) -> Result<Value, TemplateErrorReason> {
+        let expected = |expected| TemplateErrorReason::FilterInput {
+            filter: self.name(),
+            expected,
+        };
+        let number = |value: &Value| value.as_u64().ok_or_else(|| expected("a whole number"));
+        Ok(match self {
+            Filter::Ordinal => Value::from(Ordinal(number(&value)?).to_string()),
+            Filter::OrdinalWords => Value::from(OrdinalWords(number(&value)?).to_string()),
+            Filter::Cardinal => Value::from(Cardinal(number(&value)?).to_string()),
+            Filter::Capitalize => {
+                let text = value.as_str().ok_or_else(|| expected("text"))?;
+                let mut chars = text.chars();
+                Value::from(match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => String::new(),
+                })
+            }
+            Filter::Upper => Value::from(
+                value
+                    .as_str()
+                    .ok_or_else(|| expected("text"))?
+                    .to_uppercase(),
+            ),
+            Filter::Lower => Value::from(
+                value
+                    .as_str()
+                    .ok_or_else(|| expected("text"))?
+                    .to_lowercase(),
+            ),
+            Filter::Length => Value::from(match &value {
+                Value::String(text) => text.chars().count(),
+                Value::Array(items) => items.len(),
+                Value::Object(fields) => fields.len(),
+                _ => return Err(expected("text, a list or a map")),
+            }),
+            Filter::Raw => value,
+        })
+    }
+}
+
+/// A parsed template, ready to render any number of times.
+#[derive(Debug, Clone)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Template, TemplateError> {
+        Ok(Template {
+            nodes: parser::parse(source)?,
+        })
+    }
+
+    pub fn render(&self, data: &impl Serialize, format: Format) -> Result<String, TemplateError> {
+        // Serializing to a `Value` only fails for maps with non-string keys.
+        let data = serde_json::to_value(data).expect("template data serializes to JSON");
+        let mut renderer = Renderer {
+            format,
+            data: &data,
+            scopes: Vec::new(),
+            output: String::new(),
+        };
+        renderer.render(&self.nodes)?;
+        Ok(renderer.output)
+    }
+}
+
+struct Renderer<'a> {
+    format: Format,
+    data: &'a Value,
+    // The loop variables, innermost last.
+    scopes: Vec<(String, Value)>,
+    output: String,
+}
+
+impl Renderer<'_> {
+    fn render(&mut self, nodes: &[Node]) -> Result<(), TemplateError> {
+        for node in nodes {
+            match node {
+                Node::Text(text) => self.output.push_str(text),
+                Node::Output(expression) => {
+                    let value = self.evaluate(expression)?;
+                    let text = match value {
+                        Value::Null => String::new(),
+                        Value::String(text) => text,
+                        Value::Bool(_) | Value::Number(_) => value.to_string(),
+                        Value::Array(_) | Value::Object(_) => {
+                            return Err(TemplateError {
+                                line: expression.line,
+                                reason: TemplateErrorReason::NotPrintable(
+                                    expression.path.join("."),
+                                ),
+                            })
+                        }
+                    };
+                    if expression.filters.contains(&Filter::Raw) {
+                        self.output.push_str(&text);
+                    } else {
+                        self.output.push_str(&self.format.escape(&text));
+                    }
+                }
+                Node::For {
+                    variable,
+                    list,
+                    body,
+                } => {
+                    let Value::Array(items) = self.evaluate(list)? else {
+                        return Err(TemplateError {
+                            line: list.line,
+                            reason: TemplateErrorReason::NotAList(list.path.join(".")),
+                        });
+                    };
+                    let length = items.len();
+                    for (index, item) in items.into_iter().enumerate() {
+                        let mut state = Map::new();
+                        state.insert("index".to_string(), Value::from(index + 1));
+                        state.insert("index0".to_string(), Value::from(index));
+                        state.insert("first".to_string(), Value::from(index == 0));
+                        state.insert("last".to_string(), Value::from(index + 1 == length));
+                        state.insert("length".to_string(), Value::from(length));
+                        self.scopes.push(("loop".to_string(), Value::Object(state)));
+                        self.scopes.push((variable.clone(), item));
+                        let rendered = self.render(body);
+                        self.scopes.truncate(self.scopes.len() - 2);
+                        rendered?;
+                    }
+                }
+                Node::If {
+                    negated,
+                    condition,
+                    then,
+                    otherwise,
+                } => {
+                    let value = match self.evaluate(condition) {
+                        Ok(value) => value,
+                        // Testing for a missing field is how templates
+                        // check for optional data.
+                        Err(TemplateError {
+                            reason: TemplateErrorReason::Undefined(_),
+                            ..
+                        }) => Value::Null,
+                        Err(error) => return Err(error),
+                    };
+                    if is_truthy(&value) != *negated {
+                        self.render(then)?;
+                    } else {
+                        self.render(otherwise)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self, expression: &Expression) -> Result<Value, TemplateError> {
+        let undefined = || TemplateError {
+            line: expression.line,
+            reason: TemplateErrorReason::Undefined(expression.path.join(".")),
+        };
+
+        let (first, rest) = expression.path.split_first().ok_or_else(undefined)?;
+        let mut value = match self.scopes.iter().rev().find(|(name, _)| name == first) {
+            Some((_, value)) => value,
+            None => self.data.get(first).ok_or_else(undefined)?,
+        };
+        for name in rest {
+            value = value.get(name).ok_or_else(undefined)?;
+        }
+
+        let mut value = value.clone();
+        for filter in &expression.filters {
+            value = filter.apply(value).map_err(|reason| TemplateError {
+                line: expression.line,
+                reason,
+            })?;
+        }
+        Ok(value)
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(value) => *value,
+        Value::Number(number) => number.as_f64() != Some(0.0),
+        Value::String(text) => !text.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(fields) => !fields.is_empty(),
+    }
+}
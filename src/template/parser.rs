@@ -0,0 +1,253 @@
+// Turns the source of a template into a tree of nodes, checking that
+// blocks are closed and that paths and filters are valid.
+
+use super::{Filter, TemplateError, TemplateErrorReason};
+
+#[derive(Debug, Clone)]
+pub(super) enum Node {
+    Text(String),
+    Output(Expression),
+    For {
+        variable: String,
+        list: Expression,
+        body: Vec<Node>,
+    },
+    If {
+        negated: bool,
+        condition: Expression,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+}
+
+/// A path through the data, and the filters applied to its value.
+#[derive(Debug, Clone)]
+pub(super) struct Expression {
+    pub path: Vec<String>,
+    pub filters: Vec<Filter>,
+    pub line: usize,
+}
+
+enum Token {
+    Text(String),
+    Output { source: String, line: usize },
+    Tag { source: String, line: usize },
+}
+
+pub(super) fn parse(source: &str) -> Result<Vec<Node>, TemplateError> {
+    let mut tokens = tokenize(source)?.into_iter();
+    match parse_nodes(&mut tokens)? {
+        Block { nodes, end: None } => Ok(nodes),
+        Block { end: Some(end), .. } => Err(TemplateError {
+            line: end.line,
+            reason: TemplateErrorReason::UnexpectedTag(end.name),
+        }),
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, TemplateError> {
+    let line_of = |position: usize| source[..position].matches('\n').count() + 1;
+
+    let mut tokens = Vec::new();
+    let mut text = String::new();
+    let mut position = 0;
+    while let Some(offset) = source[position..].find('{') {
+        let start = position + offset;
+        let close = match source[start..].get(..2) {
+            Some("{{") => "}}",
+            Some("{%") => "%}",
+            Some("{#") => "#}",
+            _ => {
+                text.push_str(&source[position..=start]);
+                position = start + 1;
+                continue;
+            }
+        };
+        let inner_start = start + 2;
+        let inner_end = match source[inner_start..].find(close) {
+            Some(length) => inner_start + length,
+            None => {
+                return Err(TemplateError {
+                    line: line_of(start),
+                    reason: TemplateErrorReason::Unclosed(close),
+                })
+            }
+        };
+        let mut end = inner_end + 2;
+        let mut before = &source[position..start];
+
+        // A tag or comment alone on its line takes the line with it.
+        if close != "}}" {
+            let line_start = source[..start].rfind('\n').map_or(0, |index| index + 1);
+            let line_end = source[end..]
+                .find('\n')
+                .map_or(source.len(), |index| end + index + 1);
+            if line_start >= position
+                && source[line_start..start].trim().is_empty()
+                && source[end..line_end].trim().is_empty()
+            {
+                before = &source[position..line_start];
+                end = line_end;
+            }
+        }
+
+        text.push_str(before);
+        let inner = source[inner_start..inner_end].trim().to_string();
+        let line = line_of(start);
+        match close {
+            "}}" => {
+                tokens.push(Token::Text(std::mem::take(&mut text)));
+                tokens.push(Token::Output {
+                    source: inner,
+                    line,
+                });
+            }
+            "%}" => {
+                tokens.push(Token::Text(std::mem::take(&mut text)));
+                tokens.push(Token::Tag {
+                    source: inner,
+                    line,
+                });
+            }
+            _ => {}
+        }
+        position = end;
+    }
+    text.push_str(&source[position..]);
+    tokens.push(Token::Text(text));
+    tokens.retain(|token| !matches!(token, Token::Text(text) if text.is_empty()));
+    Ok(tokens)
+}
+
+// Nodes up to the end of the tokens, or up to an `else`, `endfor` or
+// `endif` tag for the caller to check.
+struct Block {
+    nodes: Vec<Node>,
+    end: Option<EndTag>,
+}
+
+struct EndTag {
+    name: String,
+    line: usize,
+}
+
+fn parse_nodes(tokens: &mut impl Iterator<Item = Token>) -> Result<Block, TemplateError> {
+    let mut nodes = Vec::new();
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Text(text) => nodes.push(Node::Text(text)),
+            Token::Output { source, line } => {
+                nodes.push(Node::Output(parse_expression(&source, line)?))
+            }
+            Token::Tag { source, line } => {
+                let invalid = || TemplateError {
+                    line,
+                    reason: TemplateErrorReason::InvalidTag(source.clone()),
+                };
+                let words: Vec<&str> = source.split_whitespace().collect();
+                match words.as_slice() {
+                    [name @ ("else" | "endfor" | "endif")] => {
+                        let end = EndTag {
+                            name: name.to_string(),
+                            line,
+                        };
+                        return Ok(Block {
+                            nodes,
+                            end: Some(end),
+                        });
+                    }
+                    ["for", variable, "in", ..] => {
+                        if !is_name(variable) {
+                            return Err(invalid());
+                        }
+                        let list = source.split_once(" in ").ok_or_else(invalid)?.1;
+                        let list = parse_expression(list, line)?;
+                        let (body, _) = parse_block(tokens, "for", line, &["endfor"])?;
+                        nodes.push(Node::For {
+                            variable: variable.to_string(),
+                            list,
+                            body,
+                        });
+                    }
+                    ["if", _, ..] => {
+                        let negated = words[1] == "not";
+                        let condition = source["if".len()..].trim_start();
+                        let condition = if negated {
+                            &condition["not".len()..]
+                        } else {
+                            condition
+                        };
+                        let condition = parse_expression(condition, line)?;
+
+                        let (then, end) = parse_block(tokens, "if", line, &["else", "endif"])?;
+                        let otherwise = if end == "else" {
+                            parse_block(tokens, "if", line, &["endif"])?.0
+                        } else {
+                            Vec::new()
+                        };
+                        nodes.push(Node::If {
+                            negated,
+                            condition,
+                            then,
+                            otherwise,
+                        });
+                    }
+                    _ => return Err(invalid()),
+                }
+            }
+        }
+    }
+    Ok(Block { nodes, end: None })
+}
+
+// The body of a block opened on `line`, and the tag among `ends` closing
+// it.
+fn parse_block(
+    tokens: &mut impl Iterator<Item = Token>,
+    opened: &'static str,
+    line: usize,
+    ends: &[&str],
+) -> Result<(Vec<Node>, String), TemplateError> {
+    let block = parse_nodes(tokens)?;
+    match block.end {
+        Some(end) if ends.contains(&end.name.as_str()) => Ok((block.nodes, end.name)),
+        Some(end) => Err(TemplateError {
+            line: end.line,
+            reason: TemplateErrorReason::UnexpectedTag(end.name),
+        }),
+        None => Err(TemplateError {
+            line,
+            reason: TemplateErrorReason::UnclosedBlock(opened),
+        }),
+    }
+}
+
+fn parse_expression(source: &str, line: usize) -> Result<Expression, TemplateError> {
+    let mut parts = source.split('|').map(str::trim);
+    let path = parts.next().unwrap_or_default();
+    let names: Vec<String> = path.split('.').map(str::to_string).collect();
+    if !names.iter().all(|name| is_name(name)) {
+        return Err(TemplateError {
+            line,
+            reason: TemplateErrorReason::InvalidPath(path.to_string()),
+        });
+    }
+
+    let filters = parts
+        .map(|name| {
+            Filter::parse(name).ok_or_else(|| TemplateError {
+                line,
+                reason: TemplateErrorReason::UnknownFilter(name.to_string()),
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Expression {
+        path: names,
+        filters,
+        line,
+    })
+}
+
+fn is_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
@@ -0,0 +1,10 @@
+// Units
+// /////
+//
+// Newtypes for physical quantities, so that the compiler rather than a
+// comment keeps a Fahrenheit value from being passed where Celsius is
+// expected.
+
+pub mod convert;
+pub mod csv;
+pub mod temperature;
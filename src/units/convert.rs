@@ -0,0 +1,268 @@
+// Conversion by Unit Name
+// ///////////////////////
+//
+// The typed units are the right tool inside a program, but a command
+// line or a data file names its units in text, such as "98.6F to C" or
+// "300 K in C".
+// This module looks units up by symbol or name and converts between any
+// two of the same dimension.
+//
+// Length, mass and time units are a fixed factor away from their SI base
+// unit. Temperatures are not - their scales have different zeros - so they
+// are converted through the validated types of `units::temperature`.
+
+use std::error::Error;
+use std::fmt;
+
+use super::temperature::{Celsius, Fahrenheit, Kelvin, Rankine, TemperatureError};
+
+use self::Dimension::{Length, Mass, Temperature, Time};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dimension {
+    Temperature,
+    Length,
+    Mass,
+    Time,
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Dimension::Temperature => "temperature",
+            Dimension::Length => "length",
+            Dimension::Mass => "mass",
+            Dimension::Time => "time",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Scale {
+    // How many SI base units (metre, kilogram, second) one unit is.
+    Factor(f64),
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+    Rankine,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Unit {
+    pub symbol: &'static str,
+    pub dimension: Dimension,
+    // Other spellings that are accepted, compared case-insensitively;
+    // the symbol itself is case-sensitive, since "Mm" is not "mm".
+    aliases: &'static [&'static str],
+    scale: Scale,
+}
+
+const fn unit(
+    symbol: &'static str,
+    dimension: Dimension,
+    scale: Scale,
+    aliases: &'static [&'static str],
+) -> Unit {
+    Unit { symbol, dimension, aliases, scale }
+}
+
+pub const UNITS: &[Unit] = &[
+    unit("°C", Temperature, Scale::Celsius, &["c", "celsius"]),
+    unit("°F", Temperature, Scale::Fahrenheit, &["f", "fahrenheit"]),
+    unit("K", Temperature, Scale::Kelvin, &["k", "kelvin"]),
+    unit("°R", Temperature, Scale::Rankine, &["r", "ra", "rankine"]),
+    unit(
+        "mm",
+        Length,
+        Scale::Factor(0.001),
+        &["millimetre", "millimetres", "millimeter", "millimeters"],
+    ),
+    unit(
+        "cm",
+        Length,
+        Scale::Factor(0.01),
+        &["centimetre", "centimetres", "centimeter", "centimeters"],
+    ),
+    unit("m", Length, Scale::Factor(1.0), &["metre", "metres", "meter", "meters"]),
+    unit(
+        "km",
+        Length,
+        Scale::Factor(1000.0),
+        &["kilometre", "kilometres", "kilometer", "kilometers"],
+    ),
+    unit("in", Length, Scale::Factor(0.0254), &["inch", "inches"]),
+    unit("ft", Length, Scale::Factor(0.3048), &["foot", "feet"]),
+    unit("yd", Length, Scale::Factor(0.9144), &["yard", "yards"]),
+    unit("mi", Length, Scale::Factor(1609.344), &["mile", "miles"]),
+    unit("mg", Mass, Scale::Factor(0.000_001), &["milligram", "milligrams"]),
+    unit("g", Mass, Scale::Factor(0.001), &["gram", "grams"]),
+    unit("kg", Mass, Scale::Factor(1.0), &["kilogram", "kilograms"]),
+    unit("t", Mass, Scale::Factor(1000.0), &["tonne", "tonnes"]),
+    unit("oz", Mass, Scale::Factor(0.028_349_523_125), &["ounce", "ounces"]),
+    unit("lb", Mass, Scale::Factor(0.453_592_37), &["lbs", "pound", "pounds"]),
+    unit("ms", Time, Scale::Factor(0.001), &["millisecond", "milliseconds"]),
+    unit("s", Time, Scale::Factor(1.0), &["sec", "second", "seconds"]),
+    unit("min", Time, Scale::Factor(60.0), &["minute", "minutes"]),
+    unit("h", Time, Scale::Factor(3600.0), &["hr", "hour", "hours"]),
+    unit("d", Time, Scale::Factor(86_400.0), &["day", "days"]),
+    unit("wk", Time, Scale::Factor(604_800.0), &["week", "weeks"]),
+];
+
+impl Unit {
+    /// Looks a unit up by its symbol, or one of its names ignoring case
+    /// and a leading degree sign.
+    pub fn parse(text: &str) -> Result<Unit, ConversionError> {
+        let name = text.trim();
+        let bare = name.trim_start_matches('°');
+        UNITS
+            .iter()
+            .find(|unit| {
+                unit.symbol == name
+                    || unit.aliases.iter().any(|alias| alias.eq_ignore_ascii_case(bare))
+            })
+            .copied()
+            .ok_or_else(|| ConversionError::UnknownUnit(name.to_string()))
+    }
+
+    /// Formats a value in this unit, e.g. "37.00°C" or "3.28 ft".
+    pub fn format(&self, value: f64, precision: usize) -> String {
+        let separator = if self.dimension == Dimension::Temperature { "" } else { " " };
+        format!("{value:.precision$}{separator}{}", self.symbol)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConversionError {
+    UnknownUnit(String),
+    InvalidNumber(String),
+    Incompatible { from: Unit, to: Unit },
+    Temperature(TemperatureError),
+    /// The expression is not of the form "<value><unit> [to|in] <unit>".
+    Syntax(String),
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConversionError::UnknownUnit(unit) => write!(f, "unknown unit: {unit:?}"),
+            ConversionError::InvalidNumber(number) => write!(f, "not a number: {number:?}"),
+            ConversionError::Incompatible { from, to } => write!(
+                f,
+                "cannot convert {} ({}) to {} ({})",
+                from.symbol, from.dimension, to.symbol, to.dimension
+            ),
+            ConversionError::Temperature(err) => write!(f, "{err}"),
+            ConversionError::Syntax(expression) => write!(
+                f,
+                "cannot read {expression:?}, expected something like \"98.6F to C\""
+            ),
+        }
+    }
+}
+
+impl Error for ConversionError {}
+
+impl From<TemperatureError> for ConversionError {
+    fn from(err: TemperatureError) -> ConversionError {
+        ConversionError::Temperature(err)
+    }
+}
+
+pub fn convert(value: f64, from: Unit, to: Unit) -> Result<f64, ConversionError> {
+    if from.dimension != to.dimension {
+        return Err(ConversionError::Incompatible { from, to });
+    }
+
+    match (from.scale, to.scale) {
+        (Scale::Factor(from), Scale::Factor(to)) => Ok(value * from / to),
+        (from, to) => {
+            let celsius = match from {
+                Scale::Celsius => Celsius::new(value)?,
+                Scale::Fahrenheit => Celsius::from(Fahrenheit::new(value)?),
+                Scale::Kelvin => Celsius::from(Kelvin::new(value)?),
+                Scale::Rankine => Celsius::from(Rankine::new(value)?),
+                Scale::Factor(_) => unreachable!("dimensions were checked above"),
+            };
+            Ok(match to {
+                Scale::Celsius => celsius.value(),
+                Scale::Fahrenheit => Fahrenheit::from(celsius).value(),
+                Scale::Kelvin => Kelvin::from(celsius).value(),
+                Scale::Rankine => Rankine::from(celsius).value(),
+                Scale::Factor(_) => unreachable!("dimensions were checked above"),
+            })
+        }
+    }
+}
+
+/// A value with its unit, and the unit to convert it to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Conversion {
+    pub value: f64,
+    pub from: Unit,
+    pub to: Unit,
+}
+
+impl Conversion {
+    pub fn result(&self) -> Result<f64, ConversionError> {
+        convert(self.value, self.from, self.to)
+    }
+}
+
+/// Splits "98.6F", "98.6 °F", "-40 celsius" or "1e3 m" into value and
+/// unit.
+pub fn parse_quantity(text: &str) -> Result<(f64, Unit), ConversionError> {
+    let text = text.trim();
+    let bytes = text.as_bytes();
+    let mantissa = |index: usize, c: u8| {
+        c.is_ascii_digit() || c == b'.' || c == b'_' || (index == 0 && (c == b'-' || c == b'+'))
+    };
+    let mut split = (0..bytes.len())
+        .find(|&index| !mantissa(index, bytes[index]))
+        .unwrap_or(bytes.len());
+
+    // An exponent is only taken as one when digits follow it, so that the
+    // "e" of a unit is not mistaken for it.
+    if matches!(bytes.get(split), Some(b'e' | b'E')) {
+        let digits = match bytes.get(split + 1) {
+            Some(b'-' | b'+') => split + 2,
+            _ => split + 1,
+        };
+        if bytes.get(digits).is_some_and(u8::is_ascii_digit) {
+            split = (digits..bytes.len())
+                .find(|&index| !(bytes[index].is_ascii_digit() || bytes[index] == b'_'))
+                .unwrap_or(bytes.len());
+        }
+    }
+    let (number, unit) = text.split_at(split);
+
+    // Numbers may be written with the `_` separators of Rust literals.
+    let value = number
+        .replace('_', "")
+        .parse()
+        .map_err(|_| ConversionError::InvalidNumber(number.to_string()))?;
+    Ok((value, Unit::parse(unit)?))
+}
+
+/// Reads an expression such as "98.6F to C", "300K C" or "5 ft in m".
+pub fn parse_expression(expression: &str) -> Result<Conversion, ConversionError> {
+    let words: Vec<&str> = expression.split_whitespace().collect();
+
+    // The target unit is the last word, optionally preceded by "to" or
+    // "in"; everything before it is the quantity.
+    let (to, rest) = match words.split_last() {
+        Some((to, rest)) if !rest.is_empty() => (to, rest),
+        _ => return Err(ConversionError::Syntax(expression.to_string())),
+    };
+    let to = Unit::parse(to)?;
+
+    // "in" is a unit as well as a keyword, so "5 in cm" is five inches
+    // in centimetres: that keyword is only dropped if a quantity remains.
+    let rest = match rest.split_last() {
+        Some((&"to", quantity)) => quantity,
+        Some((&"in", quantity)) if parse_quantity(&quantity.join(" ")).is_ok() => quantity,
+        _ => rest,
+    };
+
+    let (value, from) = parse_quantity(&rest.join(" "))?;
+    Ok(Conversion { value, from, to })
+}
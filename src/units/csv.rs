@@ -0,0 +1,292 @@
+// CSV Column Conversion
+// /////////////////////
+//
+// Converts one column of a CSV file, such as a sensor export with a
+// temperature column in Fahrenheit, either in place or into a new column
+// appended to every row.
+//
+// The file is streamed one record at a time, so its size does not matter.
+// Fields other than the converted one are copied byte for byte, quotes
+// and all, rather than parsed and written back, so nothing else in the
+// file changes.
+// Quoted fields may contain commas, doubled quotes and line breaks, as in
+// RFC 4180.
+//
+// A value that cannot be converted is reported with the line it starts
+// on, and its row is kept as it was (with an empty new column).
+// Blank lines are kept as they are, and are not rows.
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use super::convert::{convert, ConversionError, Unit};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target {
+    /// Replace the values of the converted column.
+    InPlace,
+    /// Keep the column and append a new one with this header.
+    NewColumn(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnConversion {
+    /// Header of the column to convert.
+    pub column: String,
+    pub from: Unit,
+    pub to: Unit,
+    pub target: Target,
+    /// Digits after the decimal point, the shortest exact form if `None`.
+    pub precision: Option<usize>,
+}
+
+/// A row whose value could not be converted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowError {
+    /// Line of the file on which the record starts, the header being 1.
+    pub line: u64,
+    pub reason: RowErrorReason,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowErrorReason {
+    MissingField,
+    InvalidNumber(String),
+    Conversion(ConversionError),
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.reason {
+            RowErrorReason::MissingField => write!(f, "the row has no value for the column"),
+            RowErrorReason::InvalidNumber(value) => write!(f, "not a number: {value:?}"),
+            RowErrorReason::Conversion(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+/// Errors that stop the whole conversion.
+#[derive(Debug)]
+pub enum CsvError {
+    Io(io::Error),
+    EmptyInput,
+    MissingColumn(String),
+    /// A quoted field is still open at the end of the file.
+    UnclosedQuote { line: u64 },
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CsvError::Io(err) => write!(f, "{err}"),
+            CsvError::EmptyInput => write!(f, "the input has no header row"),
+            CsvError::MissingColumn(column) => write!(f, "no column named {column:?}"),
+            CsvError::UnclosedQuote { line } => {
+                write!(f, "line {line}: a quoted field is never closed")
+            }
+        }
+    }
+}
+
+impl Error for CsvError {}
+
+impl From<io::Error> for CsvError {
+    fn from(err: io::Error) -> CsvError {
+        CsvError::Io(err)
+    }
+}
+
+/// Counts of a finished conversion.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Summary {
+    /// Data rows, not counting the header.
+    pub rows: u64,
+    pub converted: u64,
+    pub failed: u64,
+}
+
+/// Streams `input` to `output`, converting the column described by
+/// `conversion`. Every row that cannot be converted is passed to
+/// `on_error` as soon as it is read.
+pub fn convert_csv(
+    input: impl BufRead,
+    mut output: impl Write,
+    conversion: &ColumnConversion,
+    mut on_error: impl FnMut(RowError),
+) -> Result<Summary, CsvError> {
+    let mut records = Records::new(input);
+
+    let (header, _) = records.next_record()?.ok_or(CsvError::EmptyInput)?;
+    let index = split_fields(&header.text)
+        .iter()
+        .position(|field| unquote(field).trim() == conversion.column)
+        .ok_or_else(|| CsvError::MissingColumn(conversion.column.clone()))?;
+
+    output.write_all(header.text.as_bytes())?;
+    if let Target::NewColumn(name) = &conversion.target {
+        write!(output, ",{}", quote(name))?;
+    }
+    output.write_all(header.terminator.as_bytes())?;
+
+    let mut summary = Summary::default();
+    while let Some((record, line)) = records.next_record()? {
+        // Blank lines, such as one left at the end of a file, are not rows.
+        if record.text.trim().is_empty() {
+            output.write_all(record.text.as_bytes())?;
+            output.write_all(record.terminator.as_bytes())?;
+            continue;
+        }
+        summary.rows += 1;
+        let fields = split_fields(&record.text);
+
+        let converted = match fields.get(index) {
+            None => Err(RowErrorReason::MissingField),
+            Some(field) => convert_field(unquote(field).trim(), conversion),
+        };
+        let converted = match converted {
+            Ok(value) => {
+                summary.converted += 1;
+                Some(value)
+            }
+            Err(reason) => {
+                summary.failed += 1;
+                on_error(RowError { line, reason });
+                None
+            }
+        };
+
+        match (&conversion.target, converted) {
+            (Target::InPlace, Some(value)) => {
+                for (position, field) in fields.iter().enumerate() {
+                    if position > 0 {
+                        output.write_all(b",")?;
+                    }
+                    if position == index {
+                        output.write_all(value.as_bytes())?;
+                    } else {
+                        output.write_all(field.as_bytes())?;
+                    }
+                }
+            }
+            (Target::InPlace, None) => output.write_all(record.text.as_bytes())?,
+            (Target::NewColumn(_), value) => {
+                output.write_all(record.text.as_bytes())?;
+                write!(output, ",{}", value.unwrap_or_default())?;
+            }
+        }
+        output.write_all(record.terminator.as_bytes())?;
+    }
+
+    output.flush()?;
+    Ok(summary)
+}
+
+fn convert_field(value: &str, conversion: &ColumnConversion) -> Result<String, RowErrorReason> {
+    let number: f64 = value
+        .parse()
+        .map_err(|_| RowErrorReason::InvalidNumber(value.to_string()))?;
+    let converted = convert(number, conversion.from, conversion.to)
+        .map_err(RowErrorReason::Conversion)?;
+    Ok(match conversion.precision {
+        Some(precision) => format!("{converted:.precision$}"),
+        None => converted.to_string(),
+    })
+}
+
+// One record without its line terminator, which is kept so that it can be
+// written back the same way.
+struct Record {
+    text: String,
+    terminator: &'static str,
+}
+
+struct Records<R> {
+    input: R,
+    line: u64,
+}
+
+impl<R: BufRead> Records<R> {
+    fn new(input: R) -> Records<R> {
+        Records { input, line: 0 }
+    }
+
+    // Returns the next record with the line it starts on.
+    // A record goes on over several lines while a quoted field is open.
+    fn next_record(&mut self) -> Result<Option<(Record, u64)>, CsvError> {
+        let start = self.line + 1;
+        let mut text = String::new();
+        let mut open = false;
+
+        loop {
+            let read_from = text.len();
+            if self.input.read_line(&mut text)? == 0 {
+                if text.is_empty() {
+                    return Ok(None);
+                }
+                return Err(CsvError::UnclosedQuote { line: start });
+            }
+            self.line += 1;
+
+            // An even number of quotes means every quoted field is closed,
+            // since a doubled quote inside a field counts twice. Only the
+            // line just read is counted, so long fields stay linear.
+            let quotes = text[read_from..].matches('"').count();
+            open ^= !quotes.is_multiple_of(2);
+            if !open {
+                break;
+            }
+            if !text.ends_with('\n') {
+                return Err(CsvError::UnclosedQuote { line: start });
+            }
+        }
+
+        let terminator = if text.ends_with("\r\n") {
+            "\r\n"
+        } else if text.ends_with('\n') {
+            "\n"
+        } else {
+            ""
+        };
+        text.truncate(text.len() - terminator.len());
+        Ok(Some((Record { text, terminator }, start)))
+    }
+}
+
+// Splits a record at the commas outside quotes, keeping each field's raw
+// text.
+fn split_fields(record: &str) -> Vec<&str> {
+    let mut fields = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+
+    for (index, c) in record.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                fields.push(&record[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    fields.push(&record[start..]);
+    fields
+}
+
+fn unquote(field: &str) -> String {
+    let trimmed = field.trim();
+    match trimmed.strip_prefix('"').and_then(|inner| inner.strip_suffix('"')) {
+        Some(inner) => inner.replace("\"\"", "\""),
+        None => field.to_string(),
+    }
+}
+
+fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,151 @@
+// Temperature
+// ///////////
+//
+// `Celsius`, `Fahrenheit`, `Kelvin` and `Rankine` wrap an `f64` that is
+// never below absolute zero, nor NaN.
+// Values are checked once, by `new`; the `From` conversions between the
+// scales cannot fail, since a valid temperature stays valid on any scale.
+//
+//     let body = Fahrenheit::new(98.6)?;
+//     println!("{:.1}", Celsius::from(body)); // 37.0°C
+
+use std::error::Error;
+use std::fmt;
+
+/// Why a value is not a temperature.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TemperatureError {
+    BelowAbsoluteZero { value: f64, symbol: &'static str },
+    NotANumber,
+}
+
+impl fmt::Display for TemperatureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TemperatureError::BelowAbsoluteZero { value, symbol } => {
+                write!(f, "{value}{symbol} is below absolute zero")
+            }
+            TemperatureError::NotANumber => write!(f, "a temperature cannot be NaN"),
+        }
+    }
+}
+
+impl Error for TemperatureError {}
+
+// Defines a temperature scale by how its values relate to degrees Celsius.
+// Every conversion goes through Celsius, which keeps the common
+// conversions such as 0°C to 32°F exact.
+macro_rules! temperature_scale {
+    (
+        $(#[$meta:meta])*
+        $name:ident, $symbol:literal, absolute_zero: $zero:literal,
+        to_celsius: |$t:ident| $to:expr,
+        from_celsius: |$c:ident| $from:expr $(,)?
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+        pub struct $name(f64);
+
+        impl $name {
+            pub const SYMBOL: &'static str = $symbol;
+            pub const ABSOLUTE_ZERO: $name = $name($zero);
+
+            pub fn new(value: f64) -> Result<$name, TemperatureError> {
+                if value.is_nan() {
+                    Err(TemperatureError::NotANumber)
+                } else if value < $zero {
+                    Err(TemperatureError::BelowAbsoluteZero { value, symbol: $symbol })
+                } else {
+                    Ok($name(value))
+                }
+            }
+
+            pub fn value(self) -> f64 {
+                self.0
+            }
+
+            fn to_celsius(self) -> f64 {
+                let $t = self.0;
+                $to
+            }
+
+            // Rounding errors must not push a converted absolute zero
+            // below the scale's own absolute zero.
+            fn from_celsius($c: f64) -> $name {
+                $name(f64::max($from, $zero))
+            }
+        }
+
+        impl TryFrom<f64> for $name {
+            type Error = TemperatureError;
+
+            fn try_from(value: f64) -> Result<$name, TemperatureError> {
+                $name::new(value)
+            }
+        }
+
+        impl From<$name> for f64 {
+            fn from(temperature: $name) -> f64 {
+                temperature.0
+            }
+        }
+
+        // Honours a precision such as `{:.1}`.
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match f.precision() {
+                    Some(precision) => write!(f, "{:.*}{}", precision, self.0, $symbol),
+                    None => write!(f, "{}{}", self.0, $symbol),
+                }
+            }
+        }
+    };
+}
+
+// `From` between every pair of distinct scales.
+macro_rules! temperature_conversions {
+    ($($from:ident => [$($to:ident),*];)*) => {
+        $($(
+            impl From<$from> for $to {
+                fn from(temperature: $from) -> $to {
+                    $to::from_celsius(temperature.to_celsius())
+                }
+            }
+        )*)*
+    };
+}
+
+temperature_scale! {
+    /// Degrees Celsius.
+    Celsius, "°C", absolute_zero: -273.15,
+    to_celsius: |c| c,
+    from_celsius: |c| c,
+}
+
+temperature_scale! {
+    /// Degrees Fahrenheit.
+    Fahrenheit, "°F", absolute_zero: -459.67,
+    to_celsius: |f| (f - 32.0) * 5.0 / 9.0,
+    from_celsius: |c| c * 9.0 / 5.0 + 32.0,
+}
+
+temperature_scale! {
+    /// Kelvin, the SI scale starting at absolute zero.
+    Kelvin, "K", absolute_zero: 0.0,
+    to_celsius: |k| k - 273.15,
+    from_celsius: |c| c + 273.15,
+}
+
+temperature_scale! {
+    /// Degrees Rankine, absolute zero based with Fahrenheit-sized degrees.
+    Rankine, "°R", absolute_zero: 0.0,
+    to_celsius: |r| (r - 491.67) * 5.0 / 9.0,
+    from_celsius: |c| (c + 273.15) * 9.0 / 5.0,
+}
+
+temperature_conversions! {
+    Celsius => [Fahrenheit, Kelvin, Rankine];
+    Fahrenheit => [Celsius, Kelvin, Rankine];
+    Kelvin => [Celsius, Fahrenheit, Rankine];
+    Rankine => [Celsius, Fahrenheit, Kelvin];
+}
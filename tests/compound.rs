@@ -0,0 +1,243 @@
+// Tests for the bounds-checked arrays and tuples of `rust_book::compound`,
+// and for the indexer at the end of the `3_2_data_types` binary.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use rust_book::compound::{
+    CommandError, Compound, Element, ElementError, IndexError, IndexRange, Kind,
+    ParseCompoundError, Scope,
+};
+use rust_book::integers::literal::IntegerValue;
+use rust_book::integers::IntegerType;
+
+fn scope() -> Scope {
+    let mut scope = Scope::new();
+    scope.insert("a", Compound::from([1, 2, 3, 4, 5]));
+    scope.insert("months", Compound::from(["January", "February", "March"]));
+    scope.insert(
+        "tup",
+        Compound::Tuple(vec![500.into(), 6.4.into(), 1u8.into()]),
+    );
+    scope
+}
+
+#[test]
+fn indices_count_from_either_end() {
+    let scope = scope();
+    assert_eq!(scope.get("a", 0), Ok(&Element::from(1)));
+    assert_eq!(scope.get("a", 4), Ok(&Element::from(5)));
+    assert_eq!(scope.get("a", -1), Ok(&Element::from(5)));
+    assert_eq!(scope.get("a", -5), Ok(&Element::from(1)));
+    assert_eq!(scope.get("tup", -2), Ok(&Element::Float(6.4)));
+
+    assert_eq!(
+        scope.get("a", 5),
+        Err(IndexError::OutOfBounds {
+            name: "a".to_string(),
+            index: 5,
+            len: 5
+        })
+    );
+    assert_eq!(
+        scope.get("a", -6),
+        Err(IndexError::OutOfBounds {
+            name: "a".to_string(),
+            index: -6,
+            len: 5
+        })
+    );
+    assert_eq!(
+        scope.get("a", isize::MIN).unwrap_err().to_string(),
+        format!("index {} is out of bounds for a, of length 5", isize::MIN)
+    );
+    assert_eq!(
+        scope.get("b", 0),
+        Err(IndexError::UnknownName("b".to_string()))
+    );
+}
+
+#[test]
+fn elements_are_replaced_by_their_own_type() {
+    let mut scope = scope();
+    assert_eq!(scope.set("a", 1, 10.into()), Ok(Element::from(2)));
+    assert_eq!(scope.set("a", -1, 50.into()), Ok(Element::from(5)));
+    assert_eq!(
+        scope.compound("a").unwrap().to_string(),
+        "[1, 10, 3, 4, 50]"
+    );
+
+    assert_eq!(
+        scope.set("tup", 2, 1u16.into()),
+        Err(IndexError::Mismatch {
+            name: "tup".to_string(),
+            index: 2,
+            expected: Kind::Integer(IntegerType::U8),
+            found: Kind::Integer(IntegerType::U16)
+        })
+    );
+    assert!(matches!(
+        scope.set("a", 5, 6.into()),
+        Err(IndexError::OutOfBounds { .. })
+    ));
+}
+
+#[test]
+fn arrays_slice_by_range() {
+    let scope = scope();
+    let slice = |range: &str| scope.slice("a", range.parse().unwrap());
+    let elements = |values: &[i32]| {
+        values
+            .iter()
+            .map(|&value| Element::from(value))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(slice("1..3"), Ok(&elements(&[2, 3])[..]));
+    assert_eq!(slice("..=1"), Ok(&elements(&[1, 2])[..]));
+    assert_eq!(slice("-2.."), Ok(&elements(&[4, 5])[..]));
+    assert_eq!(slice(".."), Ok(&elements(&[1, 2, 3, 4, 5])[..]));
+    assert_eq!(slice("5.."), Ok(&[][..]));
+    assert_eq!(
+        slice("3..1"),
+        Err(IndexError::RangeOutOfBounds {
+            name: "a".to_string(),
+            range: IndexRange {
+                start: Some(3),
+                end: Some(1),
+                inclusive: false
+            },
+            len: 5
+        })
+    );
+    assert!(slice("..=5").is_err());
+    assert!(slice("-6..").is_err());
+
+    assert_eq!(
+        scope.slice("tup", "0..1".parse().unwrap()),
+        Err(IndexError::NotAnArray("tup".to_string()))
+    );
+    assert!("1...3".parse::<IndexRange>().is_err());
+    assert!("1..=".parse::<IndexRange>().is_err());
+    assert_eq!(
+        "-2..=-1".parse::<IndexRange>().unwrap().to_string(),
+        "-2..=-1"
+    );
+}
+
+#[test]
+fn compounds_parse_from_rust_syntax() {
+    assert_eq!(
+        "[1u8, 2, 0xff]".parse(),
+        Ok(Compound::Array(vec![1u8.into(), 2u8.into(), 255u8.into()]))
+    );
+    assert_eq!(
+        "(500, 6.4, \"a, b\",)".parse(),
+        Ok(Compound::Tuple(vec![500.into(), 6.4.into(), "a, b".into()]))
+    );
+    assert_eq!("(5,)".parse::<Compound>().unwrap().to_string(), "(5,)");
+    assert_eq!("()".parse(), Ok(Compound::Tuple(Vec::new())));
+
+    assert_eq!(
+        "[1, 2.5]".parse::<Compound>(),
+        Err(ParseCompoundError::Mixed {
+            position: 1,
+            expected: Kind::Integer(IntegerType::I32),
+            found: Kind::Float
+        })
+    );
+    assert_eq!(
+        "[1u8, 256]".parse::<Compound>().unwrap_err().to_string(),
+        "element 1: column 1: 256 is out of range for u8"
+    );
+    assert_eq!(
+        "(1, x)".parse::<Compound>(),
+        Err(ParseCompoundError::Element {
+            position: 1,
+            error: ElementError::Invalid {
+                text: "x".to_string(),
+                expected: None
+            }
+        })
+    );
+    assert!(matches!(
+        "1, 2".parse::<Compound>(),
+        Err(ParseCompoundError::Unbracketed(_))
+    ));
+    assert_eq!(
+        Element::parse("-3i64"),
+        Ok(Element::Integer(IntegerValue::I64(-3)))
+    );
+}
+
+#[test]
+fn commands() {
+    let mut scope = scope();
+    let mut run = |line: &str| scope.execute(line);
+
+    assert_eq!(run("get a 3"), Ok("4".to_string()));
+    assert_eq!(run("get months -1"), Ok("\"March\"".to_string()));
+    assert_eq!(run("set a 1 10"), Ok("a = [1, 10, 3, 4, 5]".to_string()));
+    assert_eq!(
+        run("set months 0 \"New Year\""),
+        Ok("months = [\"New Year\", \"February\", \"March\"]".to_string())
+    );
+    assert_eq!(run("slice a 1..3"), Ok("[10, 3]".to_string()));
+    assert_eq!(
+        run("let b = [true]").unwrap_err().to_string(),
+        "element 0: \"true\" is not a number or a quoted string"
+    );
+    assert_eq!(run("let b = [0b1, 0b10]"), Ok("b = [1, 2]".to_string()));
+    assert_eq!(run("show b"), Ok("b = [1, 2]".to_string()));
+
+    assert_eq!(
+        run("get a 9").unwrap_err().to_string(),
+        "index 9 is out of bounds for a, of length 5"
+    );
+    assert_eq!(
+        run("set tup 2 300").unwrap_err().to_string(),
+        "column 1: 300 is out of range for u8"
+    );
+    assert_eq!(
+        run("set tup 1 x"),
+        Err(CommandError::Element(ElementError::Invalid {
+            text: "x".to_string(),
+            expected: Some(Kind::Float)
+        }))
+    );
+    assert_eq!(run("get a"), Err(CommandError::Usage("get <name> <index>")));
+    assert_eq!(
+        run("get a one"),
+        Err(CommandError::InvalidIndex("one".to_string()))
+    );
+    assert_eq!(
+        run("let 1 = [1]"),
+        Err(CommandError::InvalidName("1".to_string()))
+    );
+    assert_eq!(
+        run("push a 6"),
+        Err(CommandError::UnknownCommand("push".to_string()))
+    );
+}
+
+#[test]
+fn indexer_reads_commands_from_stdin() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_3_2_data_types"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"get a 9\nget a -1\n\nslice months 0..2\nquit\nget a 0\n")
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert!(text.ends_with(
+        "error: index 9 is out of bounds for a, of length 5\n5\n[\"January\", \"February\"]\n"
+    ));
+}
@@ -0,0 +1,105 @@
+// Tests for the English ordinals and number words of `rust_book::english`.
+
+use rust_book::english::{Cardinal, Ordinal, OrdinalWords};
+
+#[test]
+fn ordinals() {
+    let cases = [
+        (0, "0th"),
+        (1, "1st"),
+        (2, "2nd"),
+        (3, "3rd"),
+        (4, "4th"),
+        (11, "11th"),
+        (12, "12th"),
+        (13, "13th"),
+        (21, "21st"),
+        (22, "22nd"),
+        (101, "101st"),
+        (111, "111th"),
+        (113, "113th"),
+        (1002, "1002nd"),
+        (u64::MAX, "18446744073709551615th"),
+    ];
+    for (n, expected) in cases {
+        assert_eq!(Ordinal(n).to_string(), expected);
+    }
+}
+
+#[test]
+fn cardinal_words() {
+    let cases = [
+        (0, "zero"),
+        (7, "seven"),
+        (12, "twelve"),
+        (19, "nineteen"),
+        (20, "twenty"),
+        (42, "forty-two"),
+        (100, "one hundred"),
+        (103, "one hundred and three"),
+        (999, "nine hundred and ninety-nine"),
+        (1000, "one thousand"),
+        (1001, "one thousand and one"),
+        (1100, "one thousand one hundred"),
+        (1_000_042, "one million and forty-two"),
+        (101_000, "one hundred and one thousand"),
+        (2_000_000_000, "two billion"),
+        (
+            u64::MAX,
+            "eighteen quintillion four hundred and forty-six quadrillion \
+             seven hundred and forty-four trillion seventy-three billion \
+             seven hundred and nine million five hundred and fifty-one thousand \
+             six hundred and fifteen",
+        ),
+    ];
+    for (n, expected) in cases {
+        assert_eq!(Cardinal(n).to_string(), expected);
+    }
+}
+
+#[test]
+fn ordinal_words() {
+    let cases = [
+        (0, "zeroth"),
+        (1, "first"),
+        (2, "second"),
+        (3, "third"),
+        (5, "fifth"),
+        (8, "eighth"),
+        (9, "ninth"),
+        (11, "eleventh"),
+        (12, "twelfth"),
+        (20, "twentieth"),
+        (21, "twenty-first"),
+        (42, "forty-second"),
+        (90, "ninetieth"),
+        (100, "one hundredth"),
+        (103, "one hundred and third"),
+        (1000, "one thousandth"),
+        (1_000_000, "one millionth"),
+        (
+            u64::MAX,
+            "eighteen quintillion four hundred and forty-six quadrillion \
+                    seven hundred and forty-four trillion seventy-three billion \
+                    seven hundred and nine million five hundred and fifty-one thousand \
+                    six hundred and fifteenth",
+        ),
+    ];
+    for (n, expected) in cases {
+        assert_eq!(OrdinalWords(n).to_string(), expected);
+    }
+}
+
+#[test]
+fn adapters_respect_format_strings() {
+    let line = format!(
+        "On the {} day, {} of {}",
+        OrdinalWords(12),
+        Ordinal(12),
+        Cardinal(12)
+    );
+    assert_eq!(line, "On the twelfth day, 12th of twelve");
+
+    let row = format!("|{:>6}|{:<6}|{:^7}|", Ordinal(3), Cardinal(3), OrdinalWords(3));
+    assert_eq!(row, "|   3rd|three | third |");
+}
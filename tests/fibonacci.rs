@@ -0,0 +1,196 @@
+// Cross-checks the strategies of `rust_book::fibonacci` against each
+// other and against known values.
+
+use num_bigint::BigUint;
+use rust_book::fibonacci::recurrence::{Recurrence, RecurrenceError};
+use rust_book::fibonacci::{self, Strategy};
+
+#[test]
+fn known_values() {
+    let first = [0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89, 144];
+    for (n, expected) in first.into_iter().enumerate() {
+        assert_eq!(fibonacci::iterative(n as u64), expected);
+    }
+    assert_eq!(fibonacci::fast_doubling(93), 12_200_160_415_121_876_738);
+    assert_eq!(
+        fibonacci::checked_u128(186),
+        Some(332_825_110_087_067_562_321_196_029_789_634_457_848)
+    );
+}
+
+#[test]
+fn all_strategies_agree_on_u64() {
+    for n in 0..=93 {
+        let expected = fibonacci::checked_iterative(n);
+        assert!(expected.is_some(), "F({n}) fits a u64");
+        assert_eq!(
+            fibonacci::checked_memoized(n),
+            expected,
+            "memoized, n = {n}"
+        );
+        assert_eq!(fibonacci::checked_matrix(n), expected, "matrix, n = {n}");
+        assert_eq!(
+            fibonacci::checked_fast_doubling(n),
+            expected,
+            "fast doubling, n = {n}"
+        );
+        assert_eq!(
+            fibonacci::checked_u128(n),
+            expected.map(u128::from),
+            "u128, n = {n}"
+        );
+        assert_eq!(
+            fibonacci::big(n),
+            BigUint::from(expected.unwrap()),
+            "big, n = {n}"
+        );
+    }
+
+    // The naive recursion is only affordable for small n.
+    for n in 0..=25 {
+        assert_eq!(fibonacci::recursive(n), fibonacci::iterative(n));
+    }
+}
+
+#[test]
+fn all_strategies_agree_on_u128_and_big() {
+    for n in 0..=186 {
+        let expected: Option<u128> = fibonacci::checked_with(Strategy::Iterative, n);
+        for strategy in [Strategy::Memoized, Strategy::Matrix, Strategy::FastDoubling] {
+            assert_eq!(
+                fibonacci::checked_with(strategy, n),
+                expected,
+                "{strategy:?}, n = {n}"
+            );
+        }
+        assert_eq!(fibonacci::big(n), BigUint::from(expected.unwrap()));
+    }
+
+    for n in [187, 500, 1000] {
+        let expected: BigUint = fibonacci::checked_with(Strategy::Iterative, n).unwrap();
+        let matrix: BigUint = fibonacci::checked_with(Strategy::Matrix, n).unwrap();
+        assert_eq!(matrix, expected);
+        assert_eq!(fibonacci::big(n), expected);
+    }
+}
+
+#[test]
+fn checked_variants_stop_at_overflow() {
+    for strategy in Strategy::ALL {
+        assert_eq!(
+            fibonacci::checked_with::<u64>(strategy, 94),
+            None,
+            "{strategy:?}"
+        );
+        assert_eq!(
+            fibonacci::checked_with::<u128>(strategy, 187),
+            None,
+            "{strategy:?}"
+        );
+    }
+    assert_eq!(fibonacci::checked_iterative(1000), None);
+    // Far deeper than the stack would allow the recursion to go.
+    assert_eq!(fibonacci::checked_recursive(200_000), None);
+    assert_eq!(fibonacci::checked_memoized(200_000), None);
+    assert_eq!(
+        fibonacci::checked_with::<u128>(Strategy::Memoized, u64::MAX),
+        None
+    );
+    assert_eq!(fibonacci::checked_fast_doubling(u64::MAX), None);
+}
+
+#[test]
+fn big_numbers_have_no_limit() {
+    // F(1000) has 209 digits.
+    let digits = fibonacci::big(1000).to_string();
+    assert_eq!(digits.len(), 209);
+    assert!(digits.starts_with("43466557686937456435688527675040625802564"));
+    assert!(digits.ends_with("849228875"));
+}
+
+#[test]
+#[should_panic(expected = "overflows u64")]
+fn plain_variants_panic_on_overflow() {
+    fibonacci::matrix(94);
+}
+
+#[test]
+fn sequence_yields_every_u64_then_stops() {
+    let numbers: Vec<u64> = fibonacci::sequence().collect();
+    assert_eq!(numbers.len(), 94);
+    assert_eq!(numbers[..8], [0, 1, 1, 2, 3, 5, 8, 13]);
+    assert_eq!(numbers[93], fibonacci::iterative(93));
+
+    let mut sequence = fibonacci::sequence::<u64>().skip(94);
+    assert_eq!(sequence.next(), None);
+    assert_eq!(sequence.next(), None);
+
+    assert_eq!(fibonacci::sequence::<u128>().count(), 187);
+    assert_eq!(fibonacci::sequence::<u8>().last(), Some(233));
+}
+
+#[test]
+fn sequence_agrees_with_the_strategies() {
+    for (n, number) in fibonacci::sequence::<BigUint>().take(300).enumerate() {
+        assert_eq!(number, fibonacci::big(n as u64));
+    }
+}
+
+#[test]
+fn sequence_composes_with_iterator_adapters() {
+    let even_below_100: Vec<u64> = fibonacci::sequence()
+        .take_while(|&n| n < 100)
+        .filter(|n| n % 2 == 0)
+        .collect();
+    assert_eq!(even_below_100, [0, 2, 8, 34]);
+
+    // Consecutive ratios approach the golden ratio.
+    let ratio = fibonacci::sequence::<u64>()
+        .zip(fibonacci::sequence::<u64>().skip(1))
+        .skip(40)
+        .map(|(a, b)| b as f64 / a as f64)
+        .next()
+        .unwrap();
+    assert!((ratio - 1.618_033_988_749_895).abs() < 1e-12);
+}
+
+#[test]
+fn named_recurrences() {
+    let lucas: Vec<u32> = Recurrence::lucas().take(10).collect();
+    assert_eq!(lucas, [2, 1, 3, 4, 7, 11, 18, 29, 47, 76]);
+
+    let tribonacci: Vec<u32> = Recurrence::tribonacci().take(10).collect();
+    assert_eq!(tribonacci, [0, 0, 1, 1, 2, 4, 7, 13, 24, 44]);
+
+    let pell: Vec<u32> = Recurrence::pell().take(10).collect();
+    assert_eq!(pell, [0, 1, 2, 5, 12, 29, 70, 169, 408, 985]);
+}
+
+#[test]
+fn custom_recurrences() {
+    // a(n) = 3a(n - 1) - a(n - 2) gives every other Fibonacci number.
+    let bisection = Recurrence::new(vec![3i64, -1], vec![0, 1]).unwrap();
+    let expected: Vec<i64> = fibonacci::sequence().step_by(2).take(20).collect();
+    assert_eq!(bisection.take(20).collect::<Vec<_>>(), expected);
+
+    // Powers of two, which a u8 holds up to 128.
+    let powers: Vec<u8> = Recurrence::new(vec![2], vec![1]).unwrap().collect();
+    assert_eq!(powers, [1, 2, 4, 8, 16, 32, 64, 128]);
+}
+
+#[test]
+fn invalid_recurrences() {
+    assert_eq!(
+        Recurrence::<u64>::new(vec![], vec![]).unwrap_err(),
+        RecurrenceError::Empty
+    );
+    let error = Recurrence::new(vec![1u64, 1], vec![0]).unwrap_err();
+    assert_eq!(
+        error,
+        RecurrenceError::LengthMismatch {
+            coefficients: 2,
+            seeds: 1
+        }
+    );
+    assert_eq!(error.to_string(), "2 coefficients need 2 seeds, got 1");
+}
@@ -0,0 +1,439 @@
+// End-to-end tests for `src/bin/2_guessing_game.rs`.
+// The end-to-end tests run the compiled binary with a fixed `--seed` and
+// feed it a scripted stdin, then check the transcript written to stdout.
+// The `Game` tests exercise the library core directly.
+
+use std::cmp::Ordering;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use std::path::PathBuf;
+
+use rust_book::guessing_game::{Difficulty, Game, GuessingGame, InvalidGuess, Outcome};
+use rust_book::guessing_game::hints::{self, Distance, Trend};
+use rust_book::guessing_game::scores::{Mode, Score, ScoreBoard, SCORES_ENV_VAR};
+use rust_book::guessing_game::solver::Solver;
+use rust_book::guessing_game::words::{self, Dictionary, InvalidWord, Mark, WordGame};
+
+const SEED: &str = "42";
+
+// A path in the temporary directory that is unique to this test process.
+fn scratch_file(name: &str) -> PathBuf {
+    std::env::temp_dir()
+        .join(format!("rust_book_tests_{}", std::process::id()))
+        .join(name)
+}
+
+fn run(args: &[&str], input: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_2_guessing_game"))
+        .args(args)
+        .env_remove("GUESSING_GAME_REVEAL")
+        .env(SCORES_ENV_VAR, scratch_file("binary_scores.jsonl"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to start the guessing game");
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    String::from_utf8(output.stdout).unwrap()
+}
+
+// Asks the game for the secret behind `SEED` through the reveal mode.
+fn secret_for_seed() -> u32 {
+    let transcript = run(&["--seed", SEED, "--reveal"], "");
+    transcript
+        .lines()
+        .find_map(|line| line.strip_prefix("[reveal] seed: 42, secret number: "))
+        .expect("reveal line missing")
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn same_seed_gives_same_secret() {
+    assert_eq!(secret_for_seed(), secret_for_seed());
+}
+
+#[test]
+fn scripted_game_transcript() {
+    let secret = secret_for_seed();
+    let low = if secret > 1 { secret - 1 } else { secret };
+    let high = if secret < 100 { secret + 1 } else { secret };
+    let input = format!("oops\n{low}\n{high}\n{secret}\n");
+
+    let mut expected = String::from("Guess the number!\n");
+    expected.push_str("The secret number is between 1 and 100.\n");
+    expected.push_str("Please input your guess.\nPlease type a number!\n");
+    let mut attempts = 0;
+    for guess in [low, high, secret] {
+        attempts += 1;
+        expected.push_str(&format!("Please input your guess.\nYou guessed: {guess}\n"));
+        match guess.cmp(&secret) {
+            Ordering::Less => expected.push_str("Too small!\n"),
+            Ordering::Greater => expected.push_str("Too big!\n"),
+            Ordering::Equal => {
+                expected.push_str("You win!\n");
+                expected.push_str(&format!("It took you {attempts} attempts.\n"));
+                expected.push_str("Practice game, the score is not recorded.\n");
+                break;
+            }
+        }
+    }
+
+    assert_eq!(run(&["--seed", SEED], &input), expected);
+}
+
+#[test]
+fn normal_play_never_reveals_the_secret() {
+    let transcript = run(&["--seed", SEED], "");
+    assert!(!transcript.contains("[reveal]"));
+}
+
+#[test]
+fn game_compares_guesses_with_the_secret() {
+    let mut game = Game::new(42, 1..=100);
+
+    assert_eq!(game.submit("10"), Outcome::Less);
+    assert_eq!(game.submit("90\n"), Outcome::Greater);
+    assert_eq!(game.submit("42"), Outcome::Equal);
+    assert!(game.is_won());
+    assert_eq!(game.attempts(), 3);
+    assert_eq!(game.history(), &[10, 90, 42]);
+}
+
+#[test]
+fn invalid_guesses_do_not_count_as_attempts() {
+    let mut game = Game::new(42, 1..=100);
+
+    assert_eq!(game.submit("4o"), Outcome::Invalid(InvalidGuess::NotANumber));
+    assert_eq!(game.submit("101"), Outcome::Invalid(InvalidGuess::OutOfRange));
+    assert_eq!(game.attempts(), 0);
+}
+
+#[test]
+fn game_is_lost_when_attempts_run_out() {
+    let mut game = Game::new(42, 1..=100).with_max_attempts(2);
+
+    assert_eq!(game.submit("1"), Outcome::Less);
+    assert_eq!(game.submit("2"), Outcome::OutOfAttempts);
+    assert!(game.is_over() && !game.is_won());
+    assert_eq!(game.submit("42"), Outcome::OutOfAttempts);
+}
+
+#[test]
+fn out_of_range_guesses_get_their_own_message() {
+    let transcript = run(&["--seed", SEED, "--min", "5", "--max", "9"], "-3\n12\n");
+    let rejections = transcript
+        .lines()
+        .filter(|line| *line == "Out of range! The secret number is between 5 and 9.")
+        .count();
+    assert_eq!(rejections, 2);
+}
+
+#[test]
+fn difficulty_presets() {
+    assert_eq!("hard".parse(), Ok(Difficulty::Hard));
+    assert_eq!(Difficulty::Easy.range(), 1..=10);
+    assert_eq!(Difficulty::Easy.max_attempts(), None);
+    assert_eq!(Difficulty::Hard.range(), 1..=1000);
+    assert!(Difficulty::Hard.max_attempts().is_some());
+    assert!("impossible".parse::<Difficulty>().is_err());
+}
+
+#[test]
+fn score_board_keeps_the_best_scores_per_difficulty() {
+    let path = scratch_file("ranking_scores.jsonl");
+    let _ = std::fs::remove_file(&path);
+    let board = ScoreBoard::new(&path);
+
+    let score = |player: &str, difficulty: &str, attempts, elapsed_ms| Score {
+        player: String::from(player),
+        mode: Mode::Classic,
+        difficulty: String::from(difficulty),
+        attempts,
+        elapsed_ms,
+        timestamp: 1_760_659_200,
+    };
+    board.record(&score("ann", "easy", 4, 9000)).unwrap();
+    board.record(&score("bob", "easy", 3, 5000)).unwrap();
+    board.record(&score("cid", "easy", 3, 2000)).unwrap();
+    board.record(&score("dee", "hard", 9, 1000)).unwrap();
+
+    let table = board.top(2).unwrap();
+    let easy = &table[&(Mode::Classic, String::from("easy"))];
+    let hard = &table[&(Mode::Classic, String::from("hard"))];
+    let easy: Vec<&str> = easy.iter().map(|s| s.player.as_str()).collect();
+    assert_eq!(easy, ["cid", "bob"]);
+    assert_eq!(hard.len(), 1);
+    assert_eq!(hard[0].date(), "2025-10-17");
+}
+
+#[test]
+fn speedruns_are_ranked_apart_and_by_time() {
+    let path = scratch_file("speedrun_scores.jsonl");
+    let _ = std::fs::remove_file(&path);
+    let board = ScoreBoard::new(&path);
+
+    let speedrun = Mode::Speedrun { rounds: 3 };
+    let score = |player: &str, mode, attempts, elapsed_ms| Score {
+        player: String::from(player),
+        mode,
+        difficulty: String::from("normal"),
+        attempts,
+        elapsed_ms,
+        timestamp: 0,
+    };
+    board.record(&score("ann", Mode::Classic, 5, 1000)).unwrap();
+    board.record(&score("bob", speedrun, 15, 9000)).unwrap();
+    board.record(&score("cid", speedrun, 21, 7000)).unwrap();
+
+    let table = board.top(10).unwrap();
+    assert_eq!(table.len(), 2);
+    let runs: Vec<&str> = table[&(speedrun, String::from("normal"))]
+        .iter()
+        .map(|s| s.player.as_str())
+        .collect();
+    assert_eq!(runs, ["cid", "bob"]);
+}
+
+#[test]
+fn scores_without_a_mode_are_classic() {
+    let path = scratch_file("old_scores.jsonl");
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(
+        &path,
+        r#"{"player":"ann","difficulty":"easy","attempts":3,"elapsed_ms":10,"timestamp":0}"#,
+    )
+    .unwrap();
+
+    let scores = ScoreBoard::new(&path).load().unwrap();
+    assert_eq!(scores.len(), 1);
+    assert_eq!(scores[0].mode, Mode::Classic);
+}
+
+#[test]
+fn speedrun_reports_totals() {
+    let secret = secret_for_seed();
+    // The next rounds have secrets of their own, so the script simply
+    // walks through the whole range after the first secret.
+    let mut input = format!("{secret}\n");
+    for _ in 0..2 {
+        for guess in 1..=100 {
+            input.push_str(&format!("{guess}\n"));
+        }
+    }
+
+    let transcript = run(&["--seed", SEED, "--speedrun", "2"], &input);
+    assert!(transcript.contains("Round 2 of 2."));
+    assert!(transcript.contains("Speedrun complete: 2 rounds in "));
+    assert!(transcript.contains("Mean attempts per round: "));
+    assert!(transcript.contains("Mean time per guess: "));
+}
+
+#[test]
+fn countdown_is_lost_when_time_is_up() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_2_guessing_game"))
+        .args(["--seed", SEED, "--countdown", "1"])
+        .env(SCORES_ENV_VAR, scratch_file("binary_scores.jsonl"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // Holding on to stdin keeps the game waiting for a guess that never
+    // comes.
+    let mut stdin = child.stdin.take().unwrap();
+    stdin.write_all(b"oops\n").unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    drop(stdin);
+
+    let transcript = String::from_utf8(output.stdout).unwrap();
+    assert!(transcript.contains("Please input your guess. (1 second left)"));
+    assert!(transcript.contains("Time is up!"));
+    assert!(!transcript.contains("You win!"));
+}
+
+#[test]
+fn missing_score_file_is_an_empty_table() {
+    let board = ScoreBoard::new(scratch_file("does_not_exist.jsonl"));
+    assert!(board.top(10).unwrap().is_empty());
+}
+
+#[test]
+fn solver_finds_every_number_within_the_worst_case() {
+    for secret in 1..=100 {
+        let mut solver = Solver::new(1..=100);
+        while !solver.is_solved() {
+            let guess = solver.next_guess();
+            solver.answer(guess, guess.cmp(&secret)).unwrap();
+        }
+        assert_eq!(solver.remaining(), secret..=secret);
+        assert!(solver.guesses() <= solver.worst_case());
+    }
+    assert_eq!(Solver::new(1..=100).worst_case(), 7);
+    assert_eq!(Solver::new(1..=1000).worst_case(), 10);
+}
+
+#[test]
+fn solver_detects_contradicting_answers() {
+    let mut solver = Solver::new(1..=100);
+    solver.answer(50, Ordering::Greater).unwrap();
+
+    let contradiction = solver.answer(60, Ordering::Less).unwrap_err();
+    assert_eq!(
+        contradiction.to_string(),
+        "you said 50 was too big, now 60 is too small"
+    );
+    assert_eq!(solver.remaining(), 1..=49);
+
+    let contradiction = solver.answer(1, Ordering::Greater).unwrap_err();
+    assert_eq!(
+        contradiction.to_string(),
+        "1 cannot be too big, the number is between 1 and 100"
+    );
+}
+
+#[test]
+fn hints_compare_with_the_previous_guess() {
+    assert_eq!(hints::trend(50, 30, 25), Trend::Hotter);
+    assert_eq!(hints::trend(30, 50, 25), Trend::Colder);
+    assert_eq!(hints::trend(20, 30, 25), Trend::Same);
+    assert_eq!(hints::distance(30, 25), Distance::Within(5));
+    assert_eq!(hints::distance(60, 25), Distance::Within(50));
+    assert_eq!(hints::distance(999, 1), Distance::MoreThan(250));
+}
+
+#[test]
+fn narrowing_chart_shrinks_with_every_answer() {
+    let chart = hints::narrowing_chart(1..=100, &[50, 25, 37], 37, 10);
+    let lines: Vec<&str> = chart.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].ends_with("|##########|  1..=100"));
+    assert!(lines[1].ends_with("50  too big    |#####.....|  1..=49"));
+    assert!(lines[2].ends_with("25  too small  |..###.....|  26..=49"));
+    assert!(lines[3].ends_with("37  correct    |...#......|  37..=37"));
+}
+
+fn marks(guess: &str, secret: &str) -> Vec<Mark> {
+    words::score(guess, secret).iter().map(|letter| letter.mark).collect()
+}
+
+#[test]
+fn word_score_marks_each_letter() {
+    use Mark::{Absent as A, Correct as C, Present as P};
+
+    assert_eq!(marks("crane", "crane"), [C, C, C, C, C]);
+    assert_eq!(marks("react", "crane"), [P, P, C, P, A]);
+    // The only "e" of "spell" is already matched in place, so the second
+    // "e" of "sheep" is absent.
+    assert_eq!(marks("sheep", "spell"), [C, A, C, A, P]);
+    assert_eq!(marks("llama", "spell"), [P, P, A, A, A]);
+}
+
+#[test]
+fn word_score_handles_multi_byte_letters() {
+    use Mark::{Absent as A, Correct as C, Present as P};
+
+    assert_eq!(marks("été", "été"), [C, C, C]);
+    // A plain "e" is a different letter from "é".
+    assert_eq!(marks("tée", "été"), [P, P, A]);
+    assert_eq!(marks("eté", "été"), [A, C, C]);
+}
+
+#[test]
+fn word_game_validates_guesses_and_limits_attempts() {
+    let dictionary = Dictionary::new(["été", "thé", "abc", "Lune"]);
+    assert!(dictionary.contains("lune"));
+
+    let mut game = WordGame::new(&dictionary, "été");
+    assert_eq!(game.word_length(), 3);
+    assert_eq!(game.submit("lune"), Err(InvalidWord::WrongLength { expected: 3 }));
+    assert_eq!(game.submit("xyz"), Err(InvalidWord::NotInDictionary));
+    assert_eq!(game.attempts(), 0);
+
+    for _ in 0..words::MAX_ATTEMPTS {
+        assert!(game.submit("THÉ").is_ok());
+    }
+    assert!(game.is_over() && !game.is_won());
+    assert_eq!(game.submit("été"), Err(InvalidWord::GameOver));
+}
+
+#[test]
+fn word_game_is_won_with_the_secret() {
+    let dictionary = Dictionary::new(["été", "thé"]);
+    let mut game = WordGame::new(&dictionary, "été");
+
+    game.submit("thé").unwrap();
+    game.submit("été").unwrap();
+    assert!(game.is_won());
+    assert_eq!(game.attempts(), 2);
+}
+
+// Plays any game through `GuessingGame` alone, as the binary's prompt loop
+// does, returning the answers.
+fn play<G: GuessingGame>(game: &mut G, guesses: &[&str]) -> Vec<G::Feedback> {
+    let mut answers = Vec::new();
+    for guess in guesses {
+        if game.is_over() {
+            break;
+        }
+        answers.push(game.submit(guess));
+    }
+    answers
+}
+
+#[test]
+fn both_games_play_through_the_shared_trait() {
+    let mut game = Game::new(7, 1..=10);
+    let answers = play(&mut game, &["3", "x", "7", "9"]);
+    assert_eq!(
+        answers,
+        [
+            Outcome::Less,
+            Outcome::Invalid(InvalidGuess::NotANumber),
+            Outcome::Equal
+        ]
+    );
+    assert!(game.is_won());
+    assert_eq!(GuessingGame::attempts(&game), 2);
+
+    let dictionary = Dictionary::new(["été", "thé"]);
+    let mut game = WordGame::new(&dictionary, "été");
+    let answers = play(&mut game, &["lune", "thé", "été", "thé"]);
+    assert_eq!(answers.len(), 3);
+    assert_eq!(answers[0], Err(InvalidWord::WrongLength { expected: 3 }));
+    assert!(game.is_won());
+    assert_eq!(GuessingGame::attempts(&game), 2);
+}
+
+#[test]
+fn word_mode_uses_the_prompt_loop() {
+    let path = scratch_file("words.txt");
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, "# words\nlune\n").unwrap();
+    let path = path.to_str().unwrap();
+
+    let transcript = run(&["--words", path], "abc\nsoleil\nlune\n");
+    assert!(transcript.contains("Please type a word of 4 letters.\n"));
+    assert!(transcript.contains("[l][u][n][e]\nYou win!\nIt took you 1 attempts.\n"));
+
+    let transcript = run(&["--words", path], "");
+    assert!(transcript.ends_with("No more input, goodbye!\n"));
+}
+
+#[test]
+fn tui_falls_back_to_lines_without_a_terminal() {
+    let input = "50\n25\n";
+    assert_eq!(
+        run(&["--seed", SEED, "--tui"], input),
+        run(&["--seed", SEED], input)
+    );
+}
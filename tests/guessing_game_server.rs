@@ -0,0 +1,147 @@
+// Plays a full round on the multiplayer server over localhost, two
+// clients taking turns and letting the solver pick their guesses.
+
+use std::cmp::Ordering;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use rust_book::guessing_game::server::Server;
+use rust_book::guessing_game::solver::Solver;
+
+struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Client {
+    fn connect(addr: &str) -> Client {
+        let writer = TcpStream::connect(addr).unwrap();
+        writer.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let reader = BufReader::new(writer.try_clone().unwrap());
+        Client { reader, writer }
+    }
+
+    fn send(&mut self, line: &str) {
+        writeln!(self.writer, "{line}").unwrap();
+    }
+
+    fn receive(&mut self) -> String {
+        let mut line = String::new();
+        self.reader.read_line(&mut line).unwrap();
+        line.trim_end().to_string()
+    }
+}
+
+fn start_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let server = Arc::new(Server::new(1..=100, 7));
+    thread::spawn(move || server.serve(listener));
+    addr
+}
+
+#[test]
+fn players_take_turns_until_someone_wins() {
+    let addr = start_server();
+
+    let mut ann = Client::connect(&addr);
+    ann.send("JOIN lobby ann");
+    assert_eq!(ann.receive(), "WELCOME lobby 1 1 100");
+    assert_eq!(ann.receive(), "TURN ann");
+
+    let mut bob = Client::connect(&addr);
+    bob.send("JOIN lobby bob");
+    assert_eq!(bob.receive(), "WELCOME lobby 1 1 100");
+    assert_eq!(bob.receive(), "TURN ann");
+    assert_eq!(ann.receive(), "JOINED bob");
+    assert_eq!(ann.receive(), "TURN ann");
+
+    bob.send("GUESS 50");
+    assert_eq!(bob.receive(), "ERROR not your turn");
+
+    let mut solver = Solver::new(1..=100);
+    let mut clients = [(ann, "ann"), (bob, "bob")];
+    let mut turn = 0;
+    loop {
+        let guess = solver.next_guess();
+        clients[turn].0.send(&format!("GUESS {guess}"));
+
+        let result = clients[0].0.receive();
+        assert_eq!(clients[1].0.receive(), result);
+
+        let name = clients[turn].1;
+        turn = 1 - turn;
+        let next = clients[turn].1;
+
+        if result.starts_with("WIN") {
+            assert_eq!(result, format!("WIN {name} {guess} {}", solver.guesses() + 1));
+            for (client, _) in &mut clients {
+                assert_eq!(client.receive(), "ROUND 2 1 100");
+                assert_eq!(client.receive(), format!("TURN {next}"));
+            }
+            break;
+        }
+
+        let answer = if result == format!("RESULT {name} {guess} TOO_SMALL") {
+            Ordering::Less
+        } else {
+            assert_eq!(result, format!("RESULT {name} {guess} TOO_BIG"));
+            Ordering::Greater
+        };
+        solver.answer(guess, answer).unwrap();
+
+        for (client, _) in &mut clients {
+            assert_eq!(client.receive(), format!("TURN {next}"));
+        }
+    }
+}
+
+#[test]
+fn leaving_passes_the_turn_on() {
+    let addr = start_server();
+
+    let mut ann = Client::connect(&addr);
+    ann.send("JOIN den ann");
+    ann.receive();
+    ann.receive();
+
+    let mut bob = Client::connect(&addr);
+    bob.send("JOIN den bob");
+    bob.receive();
+    bob.receive();
+    ann.receive();
+    ann.receive();
+
+    ann.send("QUIT");
+    assert_eq!(ann.receive(), "BYE");
+    assert_eq!(bob.receive(), "LEFT ann");
+    assert_eq!(bob.receive(), "TURN bob");
+}
+
+#[test]
+fn a_connection_failing_still_leaves_the_room() {
+    let addr = start_server();
+
+    let mut ann = Client::connect(&addr);
+    ann.send("JOIN cellar ann");
+    ann.receive();
+    ann.receive();
+
+    let mut bob = Client::connect(&addr);
+    bob.send("JOIN cellar bob");
+    bob.receive();
+    bob.receive();
+
+    // A line that is not UTF-8 fails the read on the server, as a reset
+    // connection would, while ann holds the turn.
+    ann.writer.write_all(b"GUESS \xff\n").unwrap();
+    assert_eq!(bob.receive(), "LEFT ann");
+    assert_eq!(bob.receive(), "TURN bob");
+
+    bob.send("GUESS 50");
+    let result = bob.receive();
+    assert!(result.starts_with("RESULT bob 50") || result.starts_with("WIN bob 50"));
+}
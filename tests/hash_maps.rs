@@ -0,0 +1,15 @@
+// Tests for the word count of `rust_book::hash_maps`.
+
+use std::collections::HashMap;
+
+use rust_book::hash_maps::word_count;
+
+#[test]
+fn words_are_counted() {
+    let counts = word_count("Hello world wonderful world");
+    assert_eq!(
+        counts,
+        HashMap::from([("Hello", 1), ("world", 2), ("wonderful", 1)])
+    );
+    assert!(word_count(" \n ").is_empty());
+}
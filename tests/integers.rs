@@ -0,0 +1,360 @@
+// Tests for `rust_book::integers`, and for the `overflow` binary.
+
+use std::process::Command;
+
+use rust_book::integers::literal::{
+    parse, parse_as, parse_literal, IntegerValue, LiteralError, LiteralErrorReason,
+};
+use rust_book::integers::overflow::{
+    compare, compare_as, ArithmeticError, Checked, CompareError, Operation, Operator,
+    OverflowPolicy, Overflowing, Saturating, Wrapping,
+};
+use rust_book::integers::IntegerType;
+
+#[test]
+fn policies_on_overflow() {
+    assert_eq!(Wrapping::apply(Operation::Add, 250u8, 10), Ok(4));
+    assert_eq!(Checked::apply(Operation::Add, 250u8, 10), Ok(None));
+    assert_eq!(Overflowing::apply(Operation::Add, 250u8, 10), Ok((4, true)));
+    assert_eq!(Saturating::apply(Operation::Add, 250u8, 10), Ok(255));
+
+    assert_eq!(Wrapping::apply(Operation::Sub, 0u32, 1), Ok(u32::MAX));
+    assert_eq!(Saturating::apply(Operation::Sub, i16::MIN, 1), Ok(i16::MIN));
+    assert_eq!(Saturating::apply(Operation::Mul, -100i8, 2), Ok(i8::MIN));
+    assert_eq!(
+        Overflowing::apply(Operation::Pow, 2i64, 63),
+        Ok((i64::MIN, true))
+    );
+    assert_eq!(
+        Checked::apply(Operation::Pow, 3u128, 80),
+        Ok(Some(3u128.pow(80)))
+    );
+
+    // MIN / -1 and -MIN are the only signed overflows of `Div` and `Neg`.
+    assert_eq!(
+        Overflowing::apply(Operation::Div, i8::MIN, -1),
+        Ok((i8::MIN, true))
+    );
+    assert_eq!(Saturating::apply(Operation::Div, i8::MIN, -1), Ok(i8::MAX));
+    assert_eq!(Wrapping::apply(Operation::Neg, i32::MIN, 0), Ok(i32::MIN));
+    assert_eq!(Saturating::apply(Operation::Neg, i32::MIN, 0), Ok(i32::MAX));
+    assert_eq!(Checked::apply(Operation::Neg, 5u8, 0), Ok(None));
+    assert_eq!(Checked::apply(Operation::Neg, 0u8, 0), Ok(Some(0)));
+}
+
+#[test]
+fn policies_agree_without_overflow() {
+    for operation in [
+        Operation::Add,
+        Operation::Sub,
+        Operation::Mul,
+        Operation::Div,
+        Operation::Pow,
+    ] {
+        let expected = Wrapping::apply(operation, 12i64, 3).unwrap();
+        assert_eq!(Checked::apply(operation, 12i64, 3), Ok(Some(expected)));
+        assert_eq!(
+            Overflowing::apply(operation, 12i64, 3),
+            Ok((expected, false))
+        );
+        assert_eq!(Saturating::apply(operation, 12i64, 3), Ok(expected));
+        assert_eq!(Operator::apply(operation, 12i64, 3), Ok(Ok(expected)));
+    }
+}
+
+#[test]
+fn the_operator_panics_in_debug_builds() {
+    let result = Operator::apply(Operation::Add, 250u8, 10).unwrap();
+    if cfg!(debug_assertions) {
+        assert_eq!(result, Err("attempt to add with overflow".to_string()));
+    } else {
+        assert_eq!(result, Ok(4));
+    }
+
+    // The messages are those of the operators' own panics.
+    if cfg!(debug_assertions) {
+        assert_eq!(
+            Operator::apply(Operation::Neg, i8::MIN, 0)
+                .unwrap()
+                .unwrap_err(),
+            "attempt to negate with overflow"
+        );
+        assert_eq!(
+            Operator::apply(Operation::Neg, 1u8, 0)
+                .unwrap()
+                .unwrap_err(),
+            "attempt to subtract with overflow"
+        );
+        assert_eq!(
+            Operator::apply(Operation::Pow, 2i32, 31)
+                .unwrap()
+                .unwrap_err(),
+            "attempt to multiply with overflow"
+        );
+    }
+    assert_eq!(Operator::apply(Operation::Pow, 2i32, 30), Ok(Ok(1 << 30)));
+
+    // Dividing MIN by -1 panics even without overflow checks.
+    assert_eq!(
+        Operator::apply(Operation::Div, i8::MIN, -1).unwrap(),
+        Err("attempt to divide with overflow".to_string())
+    );
+}
+
+#[test]
+fn division_by_zero_and_bad_exponents_are_errors() {
+    assert_eq!(
+        Wrapping::apply(Operation::Div, 1u8, 0),
+        Err(ArithmeticError::DivisionByZero)
+    );
+    assert_eq!(
+        Checked::apply(Operation::Div, 1i8, 0),
+        Err(ArithmeticError::DivisionByZero)
+    );
+    assert_eq!(
+        Operator::apply(Operation::Div, 1u64, 0),
+        Err(ArithmeticError::DivisionByZero)
+    );
+    assert_eq!(
+        Saturating::apply(Operation::Pow, 2i32, -1),
+        Err(ArithmeticError::InvalidExponent)
+    );
+    assert_eq!(
+        Overflowing::apply(Operation::Pow, 1u64, u64::from(u32::MAX) + 1),
+        Err(ArithmeticError::InvalidExponent)
+    );
+}
+
+#[test]
+fn compare_side_by_side() {
+    let results = compare(Operation::Mul, 100i8, 2).unwrap();
+    let expected = [
+        ("wrapping", "-56"),
+        ("checked", "None"),
+        ("overflowing", "(-56, true)"),
+        ("saturating", "127"),
+    ];
+    for ((policy, result), (expected_policy, expected_result)) in results.iter().zip(expected) {
+        assert_eq!(
+            (*policy, result.as_str()),
+            (expected_policy, expected_result)
+        );
+    }
+    assert_eq!(results[4].0, "operator");
+}
+
+#[test]
+fn compare_for_a_type_chosen_at_run_time() {
+    for integer_type in IntegerType::ALL {
+        let results = compare_as(integer_type, Operation::Add, &["1", "2"]).unwrap();
+        assert_eq!(results[0].1, "3", "{integer_type}");
+    }
+
+    let results = compare_as(
+        IntegerType::U128,
+        Operation::Add,
+        &[&u128::MAX.to_string(), "1"],
+    )
+    .unwrap();
+    assert_eq!(results[3].1, u128::MAX.to_string());
+
+    assert_eq!(
+        compare_as(IntegerType::U8, Operation::Add, &["256", "1"]),
+        Err(CompareError::InvalidOperand {
+            operand: "256".to_string(),
+            error: LiteralError {
+                column: 1,
+                reason: LiteralErrorReason::OutOfRange {
+                    literal: "256".to_string(),
+                    integer_type: IntegerType::U8
+                }
+            }
+        })
+    );
+    let results = compare_as(IntegerType::U8, Operation::Add, &["0xfa", "0b1010"]).unwrap();
+    assert_eq!(results[0].1, "4");
+    assert_eq!(
+        compare_as(IntegerType::U8, Operation::Neg, &["1", "2"]),
+        Err(CompareError::WrongOperandCount {
+            expected: 1,
+            found: 2
+        })
+    );
+    assert_eq!(
+        compare_as(IntegerType::I32, Operation::Div, &["1", "0"])
+            .unwrap_err()
+            .to_string(),
+        "division by zero"
+    );
+}
+
+#[test]
+fn literals_in_every_notation() {
+    assert_eq!(parse_literal("98_222"), Ok(IntegerValue::I32(98_222)));
+    assert_eq!(parse_literal("0xff"), Ok(IntegerValue::I32(0xff)));
+    assert_eq!(
+        parse_literal("0xDEAD_beef_u32"),
+        Ok(IntegerValue::U32(0xdead_beef))
+    );
+    assert_eq!(parse_literal("0o77"), Ok(IntegerValue::I32(0o77)));
+    assert_eq!(
+        parse_literal("0b1111_0000"),
+        Ok(IntegerValue::I32(0b1111_0000))
+    );
+    assert_eq!(parse_literal("b'A'"), Ok(IntegerValue::U8(b'A')));
+    assert_eq!(parse_literal(r"b'\n'"), Ok(IntegerValue::U8(b'\n')));
+    assert_eq!(parse_literal(r"b'\xff'"), Ok(IntegerValue::U8(0xff)));
+    assert_eq!(parse_literal("255u8"), Ok(IntegerValue::U8(255)));
+    assert_eq!(parse_literal("-3i64"), Ok(IntegerValue::I64(-3)));
+    assert_eq!(parse_literal("-0x80i8"), Ok(IntegerValue::I8(i8::MIN)));
+    assert_eq!(parse_literal("  7  "), Ok(IntegerValue::I32(7)));
+    assert_eq!(
+        parse_literal(&format!("{}i128", i128::MIN)),
+        Ok(IntegerValue::I128(i128::MIN))
+    );
+    assert_eq!(
+        parse_literal("255u8").unwrap().integer_type(),
+        IntegerType::U8
+    );
+    assert_eq!(parse_literal("-3i64").unwrap().to_string(), "-3");
+
+    assert_eq!(parse::<u16>("0xffff"), Ok(u16::MAX));
+    assert_eq!(parse::<u8>("b'a'"), Ok(97));
+    assert_eq!(
+        parse_as("1_000", IntegerType::U64),
+        Ok(IntegerValue::U64(1000))
+    );
+}
+
+#[test]
+fn literal_errors_name_a_column() {
+    let error = |text: &str| parse_literal(text).unwrap_err();
+    let at = |column, reason| LiteralError { column, reason };
+
+    assert_eq!(error(""), at(1, LiteralErrorReason::Empty));
+    assert_eq!(error("-"), at(2, LiteralErrorReason::MissingDigits));
+    assert_eq!(error("0x_"), at(3, LiteralErrorReason::MissingDigits));
+    assert_eq!(error("_1"), at(1, LiteralErrorReason::Unexpected('_')));
+    assert_eq!(
+        error("0b102"),
+        at(
+            5,
+            LiteralErrorReason::InvalidDigit {
+                digit: '2',
+                radix: 2
+            }
+        )
+    );
+    assert_eq!(
+        error("1e3"),
+        at(
+            2,
+            LiteralErrorReason::InvalidDigit {
+                digit: 'e',
+                radix: 10
+            }
+        )
+    );
+    assert_eq!(
+        error("12usize"),
+        at(3, LiteralErrorReason::UnknownSuffix("usize".to_string()))
+    );
+    assert_eq!(error("1 2"), at(2, LiteralErrorReason::Unexpected(' ')));
+    assert_eq!(
+        error("  256u8"),
+        at(
+            3,
+            LiteralErrorReason::OutOfRange {
+                literal: "256".to_string(),
+                integer_type: IntegerType::U8
+            }
+        )
+    );
+    assert_eq!(
+        error("-1u32").to_string(),
+        "column 1: -1 is out of range for u32"
+    );
+    assert_eq!(
+        error("3_000_000_000").to_string(),
+        "column 1: 3_000_000_000 is out of range for i32"
+    );
+    assert_eq!(
+        error(&format!("{}0", u128::MAX)).reason,
+        LiteralErrorReason::OutOfRange {
+            literal: format!("{}0", u128::MAX),
+            integer_type: IntegerType::I32
+        }
+    );
+
+    assert_eq!(error("b'A"), at(1, LiteralErrorReason::UnclosedByte));
+    assert_eq!(error("b''"), at(1, LiteralErrorReason::EmptyByte));
+    assert_eq!(error("b'é'"), at(3, LiteralErrorReason::NonAsciiByte('é')));
+    assert_eq!(
+        error(r"b'\q'"),
+        at(3, LiteralErrorReason::InvalidEscape(r"\q".to_string()))
+    );
+    assert_eq!(error("-b'A'"), at(1, LiteralErrorReason::NegativeByte));
+    assert_eq!(error("b'A'u8"), at(5, LiteralErrorReason::Unexpected('u')));
+
+    assert_eq!(
+        parse::<u16>("5u8"),
+        Err(at(
+            2,
+            LiteralErrorReason::MismatchedSuffix {
+                expected: IntegerType::U16,
+                found: IntegerType::U8
+            }
+        ))
+    );
+    assert_eq!(
+        parse::<i8>("b'A'").unwrap_err().reason,
+        LiteralErrorReason::MismatchedSuffix {
+            expected: IntegerType::I8,
+            found: IntegerType::U8
+        }
+    );
+    assert_eq!(
+        parse::<u8>("1i32").unwrap_err().to_string(),
+        "column 2: expected u8, found the suffix i32"
+    );
+}
+
+#[test]
+fn names_parse_back() {
+    for integer_type in IntegerType::ALL {
+        assert_eq!(integer_type.name().parse(), Ok(integer_type));
+    }
+    assert_eq!(IntegerType::I64.bits(), 64);
+    assert!(IntegerType::I8.is_signed() && !IntegerType::U128.is_signed());
+    assert!("usize".parse::<IntegerType>().is_err());
+
+    for operation in Operation::ALL {
+        assert_eq!(operation.name().parse(), Ok(operation));
+    }
+    assert_eq!("*".parse(), Ok(Operation::Mul));
+    assert_eq!("-".parse(), Ok(Operation::Sub));
+}
+
+#[test]
+fn overflow_binary() {
+    let output = Command::new(env!("CARGO_BIN_EXE_overflow"))
+        .args(["add", "u8", "250", "10"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert!(text.starts_with("250u8 + 10u8\n  wrapping     4\n  checked      None\n"));
+    assert!(text.contains("  saturating   255\n"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_overflow"))
+        .args(["div", "i8", "1", "0"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_overflow"))
+        .args(["add", "u9", "1", "1"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,85 @@
+// Tests for the LRU cache and memoized functions of `rust_book::memo`.
+
+use rust_book::memo::{LruCache, Memoized, Recurse};
+
+#[test]
+fn cache_evicts_the_least_recently_used_entry() {
+    let mut cache = LruCache::new(2);
+    assert_eq!(cache.insert("a", 1), None);
+    assert_eq!(cache.insert("b", 2), None);
+
+    // Using "a" leaves "b" as the oldest.
+    assert_eq!(cache.get(&"a"), Some(&1));
+    assert_eq!(cache.insert("c", 3), Some(("b", 2)));
+    assert!(!cache.contains(&"b"));
+    assert_eq!(cache.len(), 2);
+
+    // Replacing a value counts as a use and evicts nothing.
+    assert_eq!(cache.insert("a", 10), None);
+    assert_eq!(cache.insert("d", 4), Some(("c", 3)));
+    assert_eq!(cache.get(&"a"), Some(&10));
+    assert_eq!(cache.get(&"c"), None);
+
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(cache.capacity(), 2);
+}
+
+#[test]
+fn cache_of_capacity_zero_holds_nothing() {
+    let mut cache = LruCache::new(0);
+    assert_eq!(cache.insert(1, 'x'), Some((1, 'x')));
+    assert!(cache.is_empty());
+}
+
+fn fibonacci(capacity: usize) -> Memoized<u64, u64, impl Fn(Recurse<u64, u64>, u64) -> u64> {
+    Memoized::new(capacity, |fibonacci: Recurse<u64, u64>, n| {
+        if n < 2 {
+            n
+        } else {
+            fibonacci(n - 1) + fibonacci(n - 2)
+        }
+    })
+}
+
+#[test]
+fn recursive_calls_go_through_the_cache() {
+    let mut memoized = fibonacci(100);
+    assert_eq!(memoized.call(90), 2_880_067_194_370_816_120);
+    // F(0) to F(90) are each computed once.
+    assert_eq!(memoized.misses(), 91);
+    assert_eq!(memoized.hits(), 88);
+
+    assert_eq!(memoized.call(50), 12_586_269_025);
+    assert_eq!(memoized.misses(), 91);
+    assert_eq!(memoized.hits(), 89);
+}
+
+#[test]
+fn a_small_cache_still_computes_each_value_once() {
+    // Computing F(n) only looks back at F(n - 1) and F(n - 2), which the
+    // call for F(n - 1) leaves among the three most recently used entries.
+    let mut memoized = fibonacci(3);
+    assert_eq!(memoized.call(90), 2_880_067_194_370_816_120);
+    assert_eq!(memoized.misses(), 91);
+    assert_eq!(memoized.cache().len(), 3);
+}
+
+#[test]
+fn without_a_cache_the_recursion_is_naive() {
+    let mut memoized = fibonacci(0);
+    assert_eq!(memoized.call(20), 6765);
+    // F(20) takes 2F(21) - 1 calls.
+    assert_eq!(memoized.misses(), 2 * 10946 - 1);
+    assert_eq!(memoized.hits(), 0);
+}
+
+#[test]
+fn non_recursive_functions_ignore_the_callback() {
+    let mut length = Memoized::new(10, |_: Recurse<String, usize>, text: String| {
+        text.chars().count()
+    });
+    assert_eq!(length.call("häagen".to_string()), 6);
+    assert_eq!(length.call("häagen".to_string()), 6);
+    assert_eq!((length.hits(), length.misses()), (1, 1));
+}
@@ -0,0 +1,19 @@
+// Tests for the `first_word` versions of `rust_book::slice_type`.
+
+use rust_book::slice_type::{first_word, first_word_2, first_word_3};
+
+#[test]
+fn first_word_versions_agree() {
+    for (text, word) in [
+        ("hello world", "hello"),
+        ("hello", "hello"),
+        ("", ""),
+        (" x", ""),
+    ] {
+        let text = String::from(text);
+        assert_eq!(first_word(&text), word.len());
+        assert_eq!(first_word_2(&text), word);
+        assert_eq!(first_word_3(&text), word);
+    }
+    assert_eq!(first_word_3("string literal"), "string");
+}
@@ -0,0 +1,265 @@
+// Tests for the cumulative songs of `rust_book::songs`, and for the `sing`
+// binary.
+
+use std::env;
+use std::fs;
+use std::process::Command;
+
+use rust_book::songs::{Song, SongError, BUILT_IN};
+use rust_book::template::{Format, Template};
+
+#[test]
+fn built_in_songs_are_valid() {
+    for (name, _) in BUILT_IN {
+        let song = Song::built_in(name).unwrap();
+        assert_eq!(song.verses().count(), song.items.len(), "{name}");
+    }
+    assert_eq!(Song::built_in("yellow-submarine"), None);
+}
+
+#[test]
+fn twelve_days_has_ordinals_and_the_partridge() {
+    let song = Song::built_in("twelve-days").unwrap();
+    assert_eq!(song.verse_count(), 12);
+
+    let first = song.verse(1).unwrap();
+    assert_eq!(
+        first,
+        [
+            "On the first day of Christmas my true love gave to me:",
+            "A partridge in a pear tree",
+        ]
+    );
+
+    let openings: Vec<String> = song.verses().map(|verse| verse[0].clone()).collect();
+    for (ordinal, opening) in ["first", "second", "third", "fourth", "eleventh", "twelfth"]
+        .iter()
+        .zip([0, 1, 2, 3, 10, 11].map(|index| &openings[index]))
+    {
+        assert_eq!(
+            *opening,
+            format!("On the {ordinal} day of Christmas my true love gave to me:")
+        );
+    }
+
+    let last = song.verse(12).unwrap();
+    assert_eq!(last.len(), 13);
+    assert_eq!(last[1], "Twelve drummers drumming");
+    assert_eq!(last[11], "Two turtle doves");
+    assert_eq!(last[12], "And a partridge in a pear tree");
+    assert_eq!(song.verse(0), None);
+    assert_eq!(song.verse(13), None);
+}
+
+#[test]
+fn old_macdonald_closes_every_verse() {
+    let song = Song::built_in("old-macdonald").unwrap();
+    let second = song.verse(2).unwrap();
+    assert_eq!(second[1], "And on that farm he had a pig, E-I-E-I-O,");
+    assert!(second[2].starts_with("With an oink oink here"));
+    assert!(second[3].starts_with("With a moo moo here"));
+    assert_eq!(second[4], "Old MacDonald had a farm, E-I-E-I-O.");
+}
+
+#[test]
+fn old_lady_comments_and_ends_with_the_horse() {
+    let song = Song::built_in("old-lady").unwrap();
+    let spider = song.verse(2).unwrap();
+    assert_eq!(
+        spider,
+        [
+            "There was an old lady who swallowed a spider.",
+            "That wriggled and jiggled and tickled inside her.",
+            "She swallowed the spider to catch the fly.",
+            "I don't know why she swallowed a fly - perhaps she'll die!",
+        ]
+    );
+
+    // The comment is only sung in the item's own verse.
+    let bird = song.verse(3).unwrap();
+    assert_eq!(
+        bird.iter()
+            .filter(|line| line.starts_with("That wriggled"))
+            .count(),
+        0
+    );
+
+    let horse = song.verse(song.verse_count()).unwrap();
+    assert_eq!(
+        horse,
+        [
+            "There was an old lady who swallowed a horse.",
+            "She's dead, of course!"
+        ]
+    );
+}
+
+#[test]
+fn songs_load_from_json() {
+    let song = Song::from_json(
+        r#"{
+            "title": "Counting",
+            "opening": ["Verse {number}, the {ordinal}, brings a {name}:"],
+            "closing": ["That's all."],
+            "items": [
+                {"name": "one", "line": "one", "after_others": "and one"},
+                {"name": "two", "line": "two"},
+                {"name": "three", "line": "three"}
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        song.to_string(),
+        "Verse 1, the 1st, brings a one:\none\nThat's all.\n\
+         \n\
+         Verse 2, the 2nd, brings a two:\ntwo\nand one\nThat's all.\n\
+         \n\
+         Verse 3, the 3rd, brings a three:\nthree\ntwo\nand one\nThat's all.\n"
+    );
+}
+
+#[test]
+fn invalid_songs_are_rejected() {
+    let error = Song::from_json(r#"{"title": "Nothing", "items": []}"#).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid song: a song needs at least one item"
+    );
+
+    let error = Song::from_json(
+        r#"{"title": "Early end", "items": [{"name": "a", "ends_song": true}, {"name": "b", "line": "b"}]}"#,
+    )
+    .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid song: only the last item can end the song, not \"a\""
+    );
+
+    let error = Song::from_toml("title = \"Mute\"\n[[items]]\nname = \"a\"\n").unwrap_err();
+    assert_eq!(error.to_string(), "invalid song: \"a\" has no line");
+
+    assert!(matches!(
+        Song::from_toml("title = 3"),
+        Err(SongError::Toml(_))
+    ));
+    assert!(matches!(
+        Song::from_json(r#"{"title": "x", "items": [], "chorus": []}"#),
+        Err(SongError::Json(_))
+    ));
+}
+
+#[test]
+fn load_picks_the_format_from_the_extension() {
+    let dir = env::temp_dir().join(format!("rust_book_songs_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let toml = dir.join("song.toml");
+    fs::write(
+        &toml,
+        "title = \"T\"\n[[items]]\nname = \"a\"\nline = \"A\"\n",
+    )
+    .unwrap();
+    assert_eq!(Song::load(&toml).unwrap().to_string(), "A\n");
+
+    let json = dir.join("song.JSON");
+    fs::write(
+        &json,
+        r#"{"title": "J", "items": [{"name": "a", "line": "A"}]}"#,
+    )
+    .unwrap();
+    assert_eq!(Song::load(&json).unwrap().title, "J");
+
+    assert!(matches!(
+        Song::load(dir.join("song.yaml")),
+        Err(SongError::UnknownFormat(_))
+    ));
+    assert!(matches!(
+        Song::load(dir.join("missing.toml")),
+        Err(SongError::Io(_))
+    ));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn songs_render_through_templates() {
+    let song = Song::built_in("twelve-days").unwrap();
+
+    let text = song.render(Format::Text);
+    assert!(text.starts_with(
+        "On the first day of Christmas my true love gave to me:\n\
+         \tA partridge in a pear tree\n\
+         \n\
+         On the second day of Christmas my true love gave to me:\n\
+         \tTwo turtle doves\n\
+         \tAnd a partridge in a pear tree\n\
+         \n"
+    ));
+    assert!(text.ends_with("\tAnd a partridge in a pear tree\n"));
+    assert_eq!(text.matches("\n\n").count(), 11);
+
+    let markdown = song.render(Format::Markdown);
+    assert!(
+        markdown.starts_with("# The Twelve Days of Christmas\n\n## First verse: the partridge\n\n")
+    );
+    assert!(markdown.contains("## Twelfth verse: the drummers\n"));
+    assert!(markdown.contains("Two turtle doves\\\nAnd a partridge in a pear tree\n"));
+
+    let old_lady = Song::built_in("old-lady").unwrap();
+    let html = old_lady.render(Format::Html);
+    assert!(html.starts_with("<article>\n<h1>There Was an Old Lady Who Swallowed a Fly</h1>\n"));
+    assert!(html.contains("<h2>Eighth verse</h2>\n<p>\nThere was an old lady who swallowed a horse.<br>\nShe&#39;s dead, of course!\n</p>"));
+    assert!(html.ends_with("</section>\n</article>\n"));
+}
+
+#[test]
+fn songs_render_with_custom_templates() {
+    let song = Song::built_in("old-macdonald").unwrap();
+    let template = Template::parse(
+        "{% for verse in verses %}{{ verse.number | ordinal }}: {{ verse.name | capitalize }} ({{ verse.cumulative | length }})\n{% endfor %}",
+    )
+    .unwrap();
+    assert_eq!(
+        song.render_with(&template, Format::Text).unwrap(),
+        "1st: Cow (1)\n2nd: Pig (2)\n3rd: Duck (3)\n4th: Horse (4)\n5th: Sheep (5)\n"
+    );
+
+    let template = Template::parse("{{ verses.chorus }}").unwrap();
+    assert!(song.render_with(&template, Format::Text).is_err());
+}
+
+#[test]
+fn sing_prints_a_song_or_a_verse() {
+    let output = Command::new(env!("CARGO_BIN_EXE_sing"))
+        .args(["--verse", "2", "twelve-days"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "On the second day of Christmas my true love gave to me:\n\
+         Two turtle doves\n\
+         And a partridge in a pear tree\n"
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_sing"))
+        .arg("old-macdonald")
+        .output()
+        .unwrap();
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(text.matches("had a farm, E-I-E-I-O.").count(), 5);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_sing"))
+        .args(["--verse", "9", "old-lady"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_sing"))
+        .arg("missing.toml")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+}
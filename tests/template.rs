@@ -0,0 +1,263 @@
+// Tests for the template engine of `rust_book::template`.
+
+use serde_json::json;
+
+use rust_book::template::{Format, Template, TemplateError, TemplateErrorReason};
+
+fn render(source: &str, data: serde_json::Value) -> String {
+    Template::parse(source)
+        .unwrap()
+        .render(&data, Format::Text)
+        .unwrap()
+}
+
+fn error(source: &str, data: serde_json::Value) -> TemplateError {
+    match Template::parse(source) {
+        Ok(template) => template.render(&data, Format::Text).unwrap_err(),
+        Err(error) => error,
+    }
+}
+
+#[test]
+fn prints_values_and_paths() {
+    let data = json!({"name": "world", "count": 3, "done": false, "nothing": null, "inner": {"deep": "yes"}});
+    assert_eq!(
+        render(
+            "Hello, {{ name }}! {{count}} {{ done }} [{{ nothing }}] {{ inner.deep }}",
+            data
+        ),
+        "Hello, world! 3 false [] yes"
+    );
+    assert_eq!(render("{ not a tag } {x}", json!({})), "{ not a tag } {x}");
+}
+
+#[test]
+fn filters() {
+    let data = json!({"n": 22, "word": "partridge", "shout": "Hey", "items": [1, 2, 3]});
+    assert_eq!(render("{{ n | ordinal }}", data.clone()), "22nd");
+    assert_eq!(
+        render("{{ n | ordinal_words }}", data.clone()),
+        "twenty-second"
+    );
+    assert_eq!(
+        render("{{ n | cardinal | capitalize }}", data.clone()),
+        "Twenty-two"
+    );
+    assert_eq!(render("{{ word | capitalize }}", data.clone()), "Partridge");
+    assert_eq!(
+        render("{{ shout | upper }} {{ shout | lower }}", data.clone()),
+        "HEY hey"
+    );
+    assert_eq!(
+        render("{{ items | length }} {{ word | length }}", data),
+        "3 9"
+    );
+}
+
+#[test]
+fn loops_and_loop_variables() {
+    let data = json!({"gifts": ["rings", "birds", "hens"]});
+    assert_eq!(
+        render(
+            "{% for gift in gifts %}{{ loop.index }}/{{ loop.length }} {{ gift }}{% if not loop.last %}, {% endif %}{% endfor %}",
+            data.clone()
+        ),
+        "1/3 rings, 2/3 birds, 3/3 hens"
+    );
+
+    let nested = json!({"rows": [{"cells": [1, 2]}, {"cells": [3]}]});
+    assert_eq!(
+        render(
+            "{% for row in rows %}{{ loop.index0 }}:{% for cell in row.cells %} {{ cell }}{% endfor %};{% endfor %}",
+            nested
+        ),
+        "0: 1 2;1: 3;"
+    );
+
+    // The loop variable shadows the data, but only inside the loop.
+    let shadowed = json!({"x": "outer", "xs": ["inner"]});
+    assert_eq!(
+        render("{% for x in xs %}{{ x }}{% endfor %} {{ x }}", shadowed),
+        "inner outer"
+    );
+}
+
+#[test]
+fn conditionals() {
+    let source = "{% if flag %}yes{% else %}no{% endif %}";
+    for (value, expected) in [
+        (json!(true), "yes"),
+        (json!(false), "no"),
+        (json!(0), "no"),
+        (json!(7), "yes"),
+        (json!(""), "no"),
+        (json!("x"), "yes"),
+        (json!([]), "no"),
+        (json!([1]), "yes"),
+        (json!({}), "no"),
+        (json!(null), "no"),
+    ] {
+        assert_eq!(
+            render(source, json!({ "flag": value })),
+            expected,
+            "{value}"
+        );
+    }
+
+    // A missing value is simply not set.
+    assert_eq!(render(source, json!({})), "no");
+    assert_eq!(
+        render("{% if not missing.field %}absent{% endif %}", json!({})),
+        "absent"
+    );
+    assert_eq!(
+        render("{% if nothing %}{% endif %}", json!({"nothing": "x"})),
+        ""
+    );
+}
+
+#[test]
+fn standalone_tags_take_their_line() {
+    let source = "\
+Gifts:
+{% for gift in gifts %}
+    {# indented, one per line #}
+\t{{ gift }}
+  {% endfor %}
+Done {% if true_value %}now{% endif %}
+";
+    assert_eq!(
+        render(
+            source,
+            json!({"gifts": ["rings", "birds"], "true_value": true})
+        ),
+        "Gifts:\n\trings\n\tbirds\nDone now\n"
+    );
+}
+
+#[test]
+fn values_are_escaped_for_the_format() {
+    let template = Template::parse("{{ text }}|{{ text | raw }}").unwrap();
+    let data = json!({"text": "<b>Tom & \"Jerry's\"</b> *1_2* #3"});
+
+    assert_eq!(
+        template.render(&data, Format::Text).unwrap(),
+        "<b>Tom & \"Jerry's\"</b> *1_2* #3|<b>Tom & \"Jerry's\"</b> *1_2* #3"
+    );
+    assert_eq!(
+        template.render(&data, Format::Html).unwrap(),
+        "&lt;b&gt;Tom &amp; &quot;Jerry&#39;s&quot;&lt;/b&gt; *1_2* #3|<b>Tom & \"Jerry's\"</b> *1_2* #3"
+    );
+    assert_eq!(
+        template.render(&data, Format::Markdown).unwrap(),
+        "\\<b\\>Tom & \"Jerry's\"\\</b\\> \\*1\\_2\\* \\#3|<b>Tom & \"Jerry's\"</b> *1_2* #3"
+    );
+}
+
+#[test]
+fn parse_errors_name_the_line() {
+    let cases = [
+        ("text\n{{ name", 2, TemplateErrorReason::Unclosed("}}")),
+        (
+            "{% for x in xs %}\n{{ x }}",
+            1,
+            TemplateErrorReason::UnclosedBlock("for"),
+        ),
+        (
+            "{% if x %}\n\n{% else %}",
+            1,
+            TemplateErrorReason::UnclosedBlock("if"),
+        ),
+        (
+            "a\n{% endif %}",
+            2,
+            TemplateErrorReason::UnexpectedTag("endif".to_string()),
+        ),
+        (
+            "{% for x in xs %}\n{% endif %}",
+            2,
+            TemplateErrorReason::UnexpectedTag("endif".to_string()),
+        ),
+        (
+            "{% while x %}",
+            1,
+            TemplateErrorReason::InvalidTag("while x".to_string()),
+        ),
+        (
+            "{{ a..b }}",
+            1,
+            TemplateErrorReason::InvalidPath("a..b".to_string()),
+        ),
+        (
+            "\n\n{{ n | roman }}",
+            3,
+            TemplateErrorReason::UnknownFilter("roman".to_string()),
+        ),
+    ];
+    for (source, line, reason) in cases {
+        assert_eq!(
+            Template::parse(source).unwrap_err(),
+            TemplateError { line, reason },
+            "{source:?}"
+        );
+    }
+}
+
+#[test]
+fn render_errors_name_the_line() {
+    let data = json!({"n": 3, "text": "x", "list": [1], "map": {"a": 1}});
+    let cases = [
+        (
+            "\n{{ missing }}",
+            2,
+            TemplateErrorReason::Undefined("missing".to_string()),
+        ),
+        (
+            "{{ map.b }}",
+            1,
+            TemplateErrorReason::Undefined("map.b".to_string()),
+        ),
+        (
+            "{% for x in text %}{% endfor %}",
+            1,
+            TemplateErrorReason::NotAList("text".to_string()),
+        ),
+        (
+            "{{ list }}",
+            1,
+            TemplateErrorReason::NotPrintable("list".to_string()),
+        ),
+        (
+            "{{ text | ordinal }}",
+            1,
+            TemplateErrorReason::FilterInput {
+                filter: "ordinal",
+                expected: "a whole number",
+            },
+        ),
+        (
+            "{{ n | upper }}",
+            1,
+            TemplateErrorReason::FilterInput {
+                filter: "upper",
+                expected: "text",
+            },
+        ),
+    ];
+    for (source, line, reason) in cases {
+        assert_eq!(
+            error(source, data.clone()),
+            TemplateError { line, reason },
+            "{source:?}"
+        );
+    }
+
+    assert_eq!(
+        error("{% if x %}\n{% endfor %}", json!({})).to_string(),
+        "line 2: unexpected {% endfor %}"
+    );
+    assert_eq!(
+        error("{% for x in xs %}", json!({})).to_string(),
+        "line 1: {% for %} is never closed by {% endfor %}"
+    );
+}
@@ -0,0 +1,224 @@
+// Tests for the typed units of `rust_book::units`, and for the `convert`
+// binary built on them.
+
+use std::process::Command;
+
+use rust_book::units::convert::{parse_expression, ConversionError, Dimension, Unit};
+use rust_book::units::csv::{convert_csv, ColumnConversion, CsvError, RowError, Summary, Target};
+use rust_book::units::temperature::{Celsius, Fahrenheit, Kelvin, Rankine, TemperatureError};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn temperatures_convert_between_all_scales() {
+    let freezing = Celsius::new(0.0).unwrap();
+    assert_eq!(Fahrenheit::from(freezing).value(), 32.0);
+    assert!(close(Kelvin::from(freezing).value(), 273.15));
+    assert!(close(Rankine::from(freezing).value(), 491.67));
+
+    let body = Fahrenheit::new(98.6).unwrap();
+    assert!(close(Celsius::from(body).value(), 37.0));
+    assert!(close(Kelvin::from(body).value(), 310.15));
+    assert!(close(Rankine::from(body).value(), 558.27));
+
+    let boiling = Kelvin::new(373.15).unwrap();
+    assert!(close(Celsius::from(boiling).value(), 100.0));
+    assert!(close(Fahrenheit::from(Rankine::from(boiling)).value(), 212.0));
+}
+
+#[test]
+fn absolute_zero_is_the_lower_bound() {
+    assert_eq!(
+        Celsius::new(-273.16),
+        Err(TemperatureError::BelowAbsoluteZero { value: -273.16, symbol: "°C" })
+    );
+    assert!(Fahrenheit::new(-460.0).is_err());
+    assert!(Kelvin::new(-0.1).is_err());
+    assert!(Rankine::try_from(-1.0).is_err());
+    assert_eq!(Kelvin::new(f64::NAN), Err(TemperatureError::NotANumber));
+
+    // Converting absolute zero never lands below it through rounding.
+    let zero = Fahrenheit::ABSOLUTE_ZERO;
+    assert!(Kelvin::from(zero).value() >= 0.0);
+    assert!(Celsius::from(zero) >= Celsius::ABSOLUTE_ZERO);
+}
+
+#[test]
+fn temperatures_display_with_their_symbol() {
+    assert_eq!(Celsius::new(21.5).unwrap().to_string(), "21.5°C");
+    assert_eq!(format!("{:.1}", Fahrenheit::new(98.6).unwrap()), "98.6°F");
+    assert_eq!(format!("{:.2}", Kelvin::new(0.0).unwrap()), "0.00K");
+    assert_eq!(Rankine::new(1.0).unwrap().to_string(), "1°R");
+    assert_eq!(
+        Celsius::new(-300.0).unwrap_err().to_string(),
+        "-300°C is below absolute zero"
+    );
+}
+
+#[test]
+fn units_are_found_by_symbol_or_name() {
+    assert_eq!(Unit::parse("C").unwrap().symbol, "°C");
+    assert_eq!(Unit::parse("°F").unwrap().symbol, "°F");
+    assert_eq!(Unit::parse("Kelvin").unwrap().symbol, "K");
+    assert_eq!(Unit::parse("feet").unwrap().dimension, Dimension::Length);
+    assert_eq!(Unit::parse("lbs").unwrap().dimension, Dimension::Mass);
+    assert_eq!(Unit::parse("minutes").unwrap().dimension, Dimension::Time);
+    assert_eq!(
+        Unit::parse("parsec"),
+        Err(ConversionError::UnknownUnit(String::from("parsec")))
+    );
+
+    // Names ignore case, but symbols do not: "Mm" is no millimetre.
+    assert_eq!(Unit::parse("FEET").unwrap().symbol, "ft");
+    for symbol in ["Mm", "M", "Mg", "MS", "KG"] {
+        assert_eq!(
+            Unit::parse(symbol),
+            Err(ConversionError::UnknownUnit(String::from(symbol)))
+        );
+    }
+}
+
+#[test]
+fn expressions_are_converted() {
+    let result = |expression| parse_expression(expression).unwrap().result().unwrap();
+
+    assert!(close(result("98.6F to C"), 37.0));
+    assert!(close(result("300K C"), 26.85));
+    assert!(close(result("-40 celsius in fahrenheit"), -40.0));
+    assert!(close(result("1 mi to km"), 1.609344));
+    assert!(close(result("5 in cm"), 12.7));
+    assert!(close(result("1_000 g to kg"), 1.0));
+    assert!(close(result("90 min to h"), 1.5));
+    assert!(close(result("1e3 m to km"), 1.0));
+    assert!(close(result("2.5E-3km in m"), 2.5));
+}
+
+#[test]
+fn bad_expressions_are_reported() {
+    let error = |expression| parse_expression(expression).and_then(|c| c.result()).unwrap_err();
+
+    assert!(matches!(error("3 kg to m"), ConversionError::Incompatible { .. }));
+    assert!(matches!(error("-500 F to C"), ConversionError::Temperature(_)));
+    assert_eq!(
+        error("3 parsecs to m"),
+        ConversionError::UnknownUnit(String::from("parsecs"))
+    );
+    assert!(matches!(error("98.6F"), ConversionError::Syntax(_)));
+    assert!(matches!(error("lots F to C"), ConversionError::InvalidNumber(_)));
+}
+
+#[test]
+fn convert_binary_prints_results_and_fails_on_unknown_units() {
+    let convert = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_convert"))
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    let output = convert(&["98.6F", "to", "C"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "37.00°C\n");
+
+    let output = convert(&["--precision", "1", "300K", "C"]);
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "26.9°C\n");
+
+    let output = convert(&["3", "furlongs", "to", "m"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr).unwrap().contains("unknown unit"));
+}
+
+fn convert_sensor_csv(input: &str, target: Target) -> (String, Vec<RowError>) {
+    let conversion = ColumnConversion {
+        column: String::from("temp_f"),
+        from: Unit::parse("F").unwrap(),
+        to: Unit::parse("C").unwrap(),
+        target,
+        precision: Some(1),
+    };
+    let mut output = Vec::new();
+    let mut errors = Vec::new();
+    convert_csv(input.as_bytes(), &mut output, &conversion, |err| errors.push(err)).unwrap();
+    (String::from_utf8(output).unwrap(), errors)
+}
+
+const SENSOR_CSV: &str = "time,temp_f,note\r\n\
+                          1,98.6,\"ok, fine\"\r\n\
+                          2,abc,\"two\nlines\"\r\n\
+                          3,32,\"say \"\"hi\"\"\"\r\n\
+                          4\r\n";
+
+#[test]
+fn csv_column_is_converted_in_place() {
+    let (output, errors) = convert_sensor_csv(SENSOR_CSV, Target::InPlace);
+
+    assert_eq!(
+        output,
+        "time,temp_f,note\r\n\
+         1,37.0,\"ok, fine\"\r\n\
+         2,abc,\"two\nlines\"\r\n\
+         3,0.0,\"say \"\"hi\"\"\"\r\n\
+         4\r\n"
+    );
+    let lines: Vec<u64> = errors.iter().map(|err| err.line).collect();
+    assert_eq!(lines, [3, 6]);
+    assert_eq!(errors[0].to_string(), "line 3: not a number: \"abc\"");
+}
+
+#[test]
+fn csv_column_is_converted_into_a_new_column() {
+    let target = Target::NewColumn(String::from("temp_c"));
+    let (output, errors) = convert_sensor_csv(SENSOR_CSV, target);
+
+    assert_eq!(
+        output,
+        "time,temp_f,note,temp_c\r\n\
+         1,98.6,\"ok, fine\",37.0\r\n\
+         2,abc,\"two\nlines\",\r\n\
+         3,32,\"say \"\"hi\"\"\",0.0\r\n\
+         4,\r\n"
+    );
+    assert_eq!(errors.len(), 2);
+}
+
+#[test]
+fn csv_blank_lines_are_passed_through() {
+    let conversion = ColumnConversion {
+        column: String::from("temp_f"),
+        from: Unit::parse("F").unwrap(),
+        to: Unit::parse("C").unwrap(),
+        target: Target::NewColumn(String::from("temp_c")),
+        precision: Some(1),
+    };
+    let mut output = Vec::new();
+    let input = "temp_f\n212\n\n32\n\n";
+    let summary = convert_csv(input.as_bytes(), &mut output, &conversion, |err| {
+        panic!("unexpected error: {err}")
+    })
+    .unwrap();
+
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "temp_f,temp_c\n212,100.0\n\n32,0.0\n\n"
+    );
+    assert_eq!(summary, Summary { rows: 2, converted: 2, failed: 0 });
+}
+
+#[test]
+fn csv_without_the_column_is_rejected() {
+    let conversion = ColumnConversion {
+        column: String::from("humidity"),
+        from: Unit::parse("F").unwrap(),
+        to: Unit::parse("C").unwrap(),
+        target: Target::InPlace,
+        precision: None,
+    };
+    let result = convert_csv("a,b\n1,2\n".as_bytes(), Vec::new(), &conversion, |_| {});
+    assert!(matches!(result, Err(CsvError::MissingColumn(_))));
+
+    let conversion = ColumnConversion { column: String::from("temp_f"), ..conversion };
+    let result = convert_csv("temp_f\n\"1\n".as_bytes(), Vec::new(), &conversion, |_| {});
+    assert!(matches!(result, Err(CsvError::UnclosedQuote { line: 2 })));
+}